@@ -0,0 +1,381 @@
+use crate::{
+    style::{Decoration, Style, StyleColor, ANSI_COLORS, RGB},
+    styled::StyledString,
+};
+use std::borrow::Cow;
+
+const ESC: char = '\x1b';
+const BEL: char = '\x07';
+const CSI_C1: char = '\u{9b}';
+
+/// Zero width code point ranges (combining marks, joiners and variation selectors).
+const ZERO_WIDTH: &[(u32, u32)] = &[
+    (0x0300, 0x036F),
+    (0x0483, 0x0489),
+    (0x0591, 0x05BD),
+    (0x05BF, 0x05BF),
+    (0x05C1, 0x05C2),
+    (0x05C4, 0x05C5),
+    (0x05C7, 0x05C7),
+    (0x0610, 0x061A),
+    (0x064B, 0x065F),
+    (0x0670, 0x0670),
+    (0x06D6, 0x06DC),
+    (0x06DF, 0x06E4),
+    (0x06E7, 0x06E8),
+    (0x06EA, 0x06ED),
+    (0x0900, 0x0902),
+    (0x093A, 0x093A),
+    (0x093C, 0x093C),
+    (0x0941, 0x0948),
+    (0x094D, 0x094D),
+    (0x0951, 0x0957),
+    (0x0E31, 0x0E31),
+    (0x0E34, 0x0E3A),
+    (0x0E47, 0x0E4E),
+    (0x1160, 0x11FF),
+    (0x1AB0, 0x1AFF),
+    (0x1DC0, 0x1DFF),
+    (0x200B, 0x200F),
+    (0x202A, 0x202E),
+    (0x2060, 0x2064),
+    (0x20D0, 0x20FF),
+    (0x302A, 0x302D),
+    (0x3099, 0x309A),
+    (0xFE00, 0xFE0F),
+    (0xFE20, 0xFE2F),
+    (0xFEFF, 0xFEFF),
+    (0x1F3FB, 0x1F3FF),
+    (0xE0000, 0xE007F),
+    (0xE0100, 0xE01EF),
+];
+
+/// Double width code point ranges (East Asian Wide and Fullwidth, plus emoji presentation).
+const WIDE: &[(u32, u32)] = &[
+    (0x1100, 0x115F),
+    (0x231A, 0x231B),
+    (0x2329, 0x232A),
+    (0x23E9, 0x23EC),
+    (0x23F0, 0x23F0),
+    (0x23F3, 0x23F3),
+    (0x25FD, 0x25FE),
+    (0x2614, 0x2615),
+    (0x2648, 0x2653),
+    (0x267F, 0x267F),
+    (0x2693, 0x2693),
+    (0x26A1, 0x26A1),
+    (0x26AA, 0x26AB),
+    (0x26BD, 0x26BE),
+    (0x26C4, 0x26C5),
+    (0x26CE, 0x26CE),
+    (0x26D4, 0x26D4),
+    (0x26EA, 0x26EA),
+    (0x26F2, 0x26F3),
+    (0x26F5, 0x26F5),
+    (0x26FA, 0x26FA),
+    (0x26FD, 0x26FD),
+    (0x2705, 0x2705),
+    (0x270A, 0x270B),
+    (0x2728, 0x2728),
+    (0x274C, 0x274C),
+    (0x274E, 0x274E),
+    (0x2753, 0x2755),
+    (0x2757, 0x2757),
+    (0x2795, 0x2797),
+    (0x27B0, 0x27B0),
+    (0x27BF, 0x27BF),
+    (0x2B1B, 0x2B1C),
+    (0x2B50, 0x2B50),
+    (0x2B55, 0x2B55),
+    (0x2E80, 0x303E),
+    (0x3041, 0x3247),
+    (0x3250, 0x4DBF),
+    (0x4E00, 0xA4CF),
+    (0xA960, 0xA97F),
+    (0xAC00, 0xD7A3),
+    (0xF900, 0xFAFF),
+    (0xFE10, 0xFE19),
+    (0xFE30, 0xFE6F),
+    (0xFF00, 0xFF60),
+    (0xFFE0, 0xFFE6),
+    (0x16FE0, 0x16FE4),
+    (0x17000, 0x18CFF),
+    (0x1B000, 0x1B2FF),
+    (0x1F004, 0x1F004),
+    (0x1F0CF, 0x1F0CF),
+    (0x1F18E, 0x1F18E),
+    (0x1F191, 0x1F19A),
+    (0x1F200, 0x1F251),
+    (0x1F300, 0x1F64F),
+    (0x1F680, 0x1F6FF),
+    (0x1F7E0, 0x1F7EB),
+    (0x1F90C, 0x1F9FF),
+    (0x1FA70, 0x1FAFF),
+    (0x20000, 0x2FFFD),
+    (0x30000, 0x3FFFD),
+];
+
+fn in_ranges(ranges: &[(u32, u32)], c: u32) -> bool {
+    ranges
+        .binary_search_by(|&(start, end)| {
+            if end < c {
+                core::cmp::Ordering::Less
+            } else if start > c {
+                core::cmp::Ordering::Greater
+            } else {
+                core::cmp::Ordering::Equal
+            }
+        })
+        .is_ok()
+}
+
+/// Work out how many terminal columns a single character occupies.
+///
+/// Control characters and combining marks are zero width, East Asian wide and fullwidth
+/// characters are two columns wide and everything else takes a single column.
+pub(crate) fn char_width(c: char) -> usize {
+    let code = c as u32;
+    if code < 0x20 || (0x7F..0xA0).contains(&code) {
+        return 0;
+    }
+    if code < 0x300 {
+        return 1;
+    }
+    if in_ranges(ZERO_WIDTH, code) {
+        0
+    } else if in_ranges(WIDE, code) {
+        2
+    } else {
+        1
+    }
+}
+
+/// Work out the width of text that is known not to contain escape sequences.
+pub(crate) fn plain_width(text: &str) -> usize {
+    text.chars().map(char_width).sum()
+}
+
+/// Work out the byte length of the escape sequence at the start of `text`.
+///
+/// `text` must start with an `ESC` or a C1 CSI character. CSI sequences end at their final
+/// byte, OSC, DCS, SOS, PM and APC strings end at `BEL` or `ESC \` and any other escape is
+/// treated as `ESC`, optional intermediate bytes and a final byte. Unterminated sequences run
+/// to the end of the text.
+pub(crate) fn escape_len(text: &str) -> usize {
+    let mut chars = text.char_indices();
+    let (_, first) = match chars.next() {
+        Some(c) => c,
+        None => return 0,
+    };
+
+    if first != CSI_C1 {
+        match chars.next() {
+            Some((_, '[')) => {}
+            Some((_, ']' | 'P' | 'X' | '^' | '_')) => {
+                let mut previous_esc = false;
+                for (i, c) in chars {
+                    if c == BEL || (previous_esc && c == '\\') {
+                        return i + 1;
+                    }
+                    previous_esc = c == ESC;
+                }
+                return text.len();
+            }
+            Some((i, ESC)) => return i,
+            Some((i, c)) => {
+                if !('\x20'..='\x2f').contains(&c) {
+                    return i + c.len_utf8();
+                }
+                for (j, c) in chars {
+                    if !('\x20'..='\x2f').contains(&c) {
+                        return j + c.len_utf8();
+                    }
+                }
+                return text.len();
+            }
+            None => return text.len(),
+        }
+    }
+
+    for (i, c) in chars {
+        if ('\x40'..='\x7e').contains(&c) {
+            return i + 1;
+        }
+    }
+    text.len()
+}
+
+/// Remove all ANSI escape sequences from a string.
+///
+/// Strips CSI sequences (including SGR styling), OSC strings such as hyperlinks and
+/// window titles, and other escape sequences. Returns the input unchanged, without
+/// allocating, when it contains no escapes.
+pub fn strip_ansi(text: &str) -> Cow<'_, str> {
+    if !text.contains([ESC, CSI_C1]) {
+        return Cow::Borrowed(text);
+    }
+
+    let mut stripped = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find([ESC, CSI_C1]) {
+        stripped.push_str(&rest[..start]);
+        rest = &rest[start..];
+        rest = &rest[escape_len(rest)..];
+    }
+    stripped.push_str(rest);
+
+    Cow::Owned(stripped)
+}
+
+/// Work out how many terminal columns a string occupies once displayed.
+///
+/// Escape sequences are ignored, East Asian wide characters count as two columns and
+/// combining marks, zero width joiners and control characters count as none.
+pub fn visible_width(text: &str) -> usize {
+    plain_width(&strip_ansi(text))
+}
+
+/// Parse text containing ANSI escape sequences into styled spans.
+///
+/// This is the inverse of what `Paint` produces. SGR sequences using the 16 colour, 256
+/// colour and RGB forms (with `;` or `:` separated parameters), decorations and resets are
+/// turned into `Style`s. All other escape sequences are dropped.
+pub fn parse_ansi(text: &str) -> StyledString {
+    let mut styled = StyledString::new();
+    let mut style = Style::new();
+    let mut rest = text;
+
+    while let Some(start) = rest.find([ESC, CSI_C1]) {
+        styled.push(style, &rest[..start]);
+        rest = &rest[start..];
+        let len = escape_len(rest);
+        if let Some(params) = sgr_params(&rest[..len]) {
+            apply_sgr(&mut style, params);
+        }
+        rest = &rest[len..];
+    }
+    styled.push(style, rest);
+
+    styled
+}
+
+/// Get the parameters of an SGR sequence, or `None` if the sequence is something else.
+fn sgr_params(sequence: &str) -> Option<&str> {
+    let params = sequence
+        .strip_prefix("\x1b[")
+        .or_else(|| sequence.strip_prefix(CSI_C1))?
+        .strip_suffix('m')?;
+
+    if params
+        .chars()
+        .all(|c| c.is_ascii_digit() || c == ';' || c == ':')
+    {
+        Some(params)
+    } else {
+        None
+    }
+}
+
+fn apply_sgr(style: &mut Style, params: &str) {
+    let groups: Vec<Vec<Option<u32>>> = params
+        .split(';')
+        .map(|param| param.split(':').map(|sub| sub.parse().ok()).collect())
+        .collect();
+
+    let mut i = 0;
+    while i < groups.len() {
+        let group = &groups[i];
+        match group[0].unwrap_or(0) {
+            0 => *style = Style::new(),
+            1 => *style = style.decoration(Decoration::Bold),
+            2 => *style = style.decoration(Decoration::Dim),
+            3 => *style = style.decoration(Decoration::Italic),
+            4 => {
+                *style = if group.get(1) == Some(&Some(0)) {
+                    style.remove_decoration(Decoration::Underline)
+                } else {
+                    style.decoration(Decoration::Underline)
+                }
+            }
+            7 => *style = style.decoration(Decoration::Invert),
+            22 => {
+                *style = style
+                    .remove_decoration(Decoration::Bold)
+                    .remove_decoration(Decoration::Dim)
+            }
+            23 => *style = style.remove_decoration(Decoration::Italic),
+            24 => *style = style.remove_decoration(Decoration::Underline),
+            27 => *style = style.remove_decoration(Decoration::Invert),
+            code @ 30..=37 => style.fg = Some(named(code - 30)),
+            code @ 40..=47 => style.bg = Some(named(code - 40)),
+            code @ 90..=97 => style.fg = Some(named(code - 90 + 8)),
+            code @ 100..=107 => style.bg = Some(named(code - 100 + 8)),
+            39 => style.fg = None,
+            49 => style.bg = None,
+            code @ (38 | 48) => {
+                let color = if group.len() > 1 {
+                    colon_color(&group[1..])
+                } else {
+                    let (color, used) = semicolon_color(&groups[i + 1..]);
+                    i += used;
+                    color
+                };
+                if let Some(color) = color {
+                    if code == 38 {
+                        style.fg = Some(color);
+                    } else {
+                        style.bg = Some(color);
+                    }
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+}
+
+fn named(index: u32) -> StyleColor {
+    StyleColor::Named(ANSI_COLORS[index as usize])
+}
+
+fn channel(value: Option<u32>) -> Option<u8> {
+    value.and_then(|v| u8::try_from(v).ok())
+}
+
+/// Read an extended colour written as `38:5:n`, `38:2:r:g:b` or `38:2:cs:r:g:b`.
+fn colon_color(sub: &[Option<u32>]) -> Option<StyleColor> {
+    match sub.first()? {
+        Some(5) => Some(StyleColor::Fixed(channel(*sub.get(1)?)?)),
+        Some(2) => {
+            let rgb = if sub.len() >= 5 {
+                &sub[2..5]
+            } else {
+                sub.get(1..4)?
+            };
+            Some(StyleColor::Rgb(RGB::new(
+                channel(rgb[0])?,
+                channel(rgb[1])?,
+                channel(rgb[2])?,
+            )))
+        }
+        _ => None,
+    }
+}
+
+/// Read an extended colour written as `38;5;n` or `38;2;r;g;b`, returning the colour and
+/// how many parameters it used.
+fn semicolon_color(params: &[Vec<Option<u32>>]) -> (Option<StyleColor>, usize) {
+    let value = |i: usize| params.get(i).and_then(|group| channel(group[0]));
+    match params.first().map(|group| group[0]) {
+        Some(Some(5)) => (value(1).map(StyleColor::Fixed), 2.min(params.len())),
+        Some(Some(2)) => {
+            let rgb = match (value(1), value(2), value(3)) {
+                (Some(r), Some(g), Some(b)) => Some(StyleColor::Rgb(RGB::new(r, g, b))),
+                _ => None,
+            };
+            (rgb, 4.min(params.len()))
+        }
+        Some(_) => (None, 1),
+        None => (None, 0),
+    }
+}
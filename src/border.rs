@@ -0,0 +1,110 @@
+use crate::env::unicode_supported;
+
+/// Border Style
+///
+/// The characters used to draw lines around and between content.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BorderStyle {
+    /// No lines at all.
+    None,
+    /// `+`, `-` and `|`, for terminals without UTF-8.
+    Ascii,
+    /// Light box drawing lines, for example `┌─┐`.
+    #[default]
+    Light,
+    /// Heavy box drawing lines, for example `┏━┓`.
+    Heavy,
+    /// Light box drawing lines with rounded corners, for example `╭─╮`.
+    Rounded,
+}
+
+/// The characters making up a border.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct BorderChars {
+    pub(crate) horizontal: char,
+    pub(crate) vertical: char,
+    pub(crate) top_left: char,
+    pub(crate) top_right: char,
+    pub(crate) bottom_left: char,
+    pub(crate) bottom_right: char,
+    pub(crate) top_junction: char,
+    pub(crate) bottom_junction: char,
+    pub(crate) left_junction: char,
+    pub(crate) right_junction: char,
+    pub(crate) cross: char,
+}
+
+const ASCII: BorderChars = BorderChars {
+    horizontal: '-',
+    vertical: '|',
+    top_left: '+',
+    top_right: '+',
+    bottom_left: '+',
+    bottom_right: '+',
+    top_junction: '+',
+    bottom_junction: '+',
+    left_junction: '+',
+    right_junction: '+',
+    cross: '+',
+};
+
+const LIGHT: BorderChars = BorderChars {
+    horizontal: '─',
+    vertical: '│',
+    top_left: '┌',
+    top_right: '┐',
+    bottom_left: '└',
+    bottom_right: '┘',
+    top_junction: '┬',
+    bottom_junction: '┴',
+    left_junction: '├',
+    right_junction: '┤',
+    cross: '┼',
+};
+
+const HEAVY: BorderChars = BorderChars {
+    horizontal: '━',
+    vertical: '┃',
+    top_left: '┏',
+    top_right: '┓',
+    bottom_left: '┗',
+    bottom_right: '┛',
+    top_junction: '┳',
+    bottom_junction: '┻',
+    left_junction: '┣',
+    right_junction: '┫',
+    cross: '╋',
+};
+
+const ROUNDED: BorderChars = BorderChars {
+    top_left: '╭',
+    top_right: '╮',
+    bottom_left: '╰',
+    bottom_right: '╯',
+    ..LIGHT
+};
+
+impl BorderStyle {
+    /// Swap box drawing borders for `BorderStyle::Ascii` when the terminal can't display UTF-8.
+    pub fn fallback(self) -> BorderStyle {
+        match self {
+            BorderStyle::Light | BorderStyle::Heavy | BorderStyle::Rounded
+                if !unicode_supported() =>
+            {
+                BorderStyle::Ascii
+            }
+            _ => self,
+        }
+    }
+
+    /// Get the characters for this border, or `None` if nothing should be drawn.
+    pub(crate) fn chars(self) -> Option<BorderChars> {
+        match self {
+            BorderStyle::None => None,
+            BorderStyle::Ascii => Some(ASCII),
+            BorderStyle::Light => Some(LIGHT),
+            BorderStyle::Heavy => Some(HEAVY),
+            BorderStyle::Rounded => Some(ROUNDED),
+        }
+    }
+}
@@ -0,0 +1,442 @@
+//! Render coloured unified diffs between two texts, with changed words emphasised.
+//!
+//! ```rust
+//! use rustyhues::diff::Diff;
+//!
+//! let old = "name = \"app\"\nversion = \"1.0\"\n";
+//! let new = "name = \"app\"\nversion = \"2.0\"\n";
+//! println!("{}", Diff::new().labels("Cargo.toml", "Cargo.toml").diff(old, new));
+//! ```
+use crate::{
+    env::PredefinedStyleType,
+    style::{Color, Decoration, Style, StyleColor},
+    styled::StyledString,
+};
+
+/// Diff
+///
+/// Settings for rendering a unified diff: styles, the number of context lines around each
+/// change and whether changed words within a line are emphasised.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Diff {
+    insert_style: Style,
+    delete_style: Style,
+    insert_emphasis: Style,
+    delete_emphasis: Style,
+    context_style: Style,
+    hunk_header_style: Style,
+    file_header_style: Style,
+    context: usize,
+    word_diff: bool,
+    labels: Option<(String, String)>,
+}
+
+impl Diff {
+    /// Create new Diff settings. Insertions use the success style and deletions the error
+    /// style, with 3 lines of context.
+    pub fn new() -> Self {
+        let insert_style = Style::predefined(PredefinedStyleType::Success);
+        let delete_style = Style::predefined(PredefinedStyleType::Error);
+        Self {
+            insert_style,
+            delete_style,
+            insert_emphasis: emphasis(insert_style, Color::Green),
+            delete_emphasis: emphasis(delete_style, Color::Red),
+            context_style: Style::new().decoration(Decoration::Dim),
+            hunk_header_style: Style::new().fg(Color::Cyan),
+            file_header_style: Style::new().decoration(Decoration::Bold),
+            context: 3,
+            word_diff: true,
+            labels: None,
+        }
+    }
+
+    /// Set the style of inserted lines. Changed words are emphasised with the same style in
+    /// bold, on a background of its foreground colour.
+    pub fn insert_style(mut self, style: Style) -> Self {
+        self.insert_style = style;
+        self.insert_emphasis = emphasis(style, Color::Green);
+        self
+    }
+
+    /// Set the style of deleted lines. Changed words are emphasised with the same style in
+    /// bold, on a background of its foreground colour.
+    pub fn delete_style(mut self, style: Style) -> Self {
+        self.delete_style = style;
+        self.delete_emphasis = emphasis(style, Color::Red);
+        self
+    }
+
+    /// Set the style of changed words within inserted lines.
+    pub fn insert_emphasis(mut self, style: Style) -> Self {
+        self.insert_emphasis = style;
+        self
+    }
+
+    /// Set the style of changed words within deleted lines.
+    pub fn delete_emphasis(mut self, style: Style) -> Self {
+        self.delete_emphasis = style;
+        self
+    }
+
+    /// Set the style of unchanged context lines. Defaults to dim.
+    pub fn context_style(mut self, style: Style) -> Self {
+        self.context_style = style;
+        self
+    }
+
+    /// Set the style of `@@ -1,3 +1,4 @@` hunk headers. Defaults to cyan.
+    pub fn hunk_header_style(mut self, style: Style) -> Self {
+        self.hunk_header_style = style;
+        self
+    }
+
+    /// Set the style of the `---` and `+++` file headers. Defaults to bold.
+    pub fn file_header_style(mut self, style: Style) -> Self {
+        self.file_header_style = style;
+        self
+    }
+
+    /// Set the number of unchanged lines shown around each change.
+    pub fn context(mut self, lines: usize) -> Self {
+        self.context = lines;
+        self
+    }
+
+    /// Turn emphasis of changed words within a line on or off. Defaults to on.
+    pub fn word_diff(mut self, enabled: bool) -> Self {
+        self.word_diff = enabled;
+        self
+    }
+
+    /// Show `---` and `+++` file headers with these names.
+    pub fn labels(mut self, old: impl Into<String>, new: impl Into<String>) -> Self {
+        self.labels = Some((old.into(), new.into()));
+        self
+    }
+
+    /// Render the differences between two texts as styled lines joined with `\n`. Identical
+    /// texts give an empty StyledString.
+    pub fn diff(&self, old: &str, new: &str) -> StyledString {
+        let old: Vec<&str> = old.lines().collect();
+        let new: Vec<&str> = new.lines().collect();
+        let ops = diff_ops(&old, &new);
+
+        let mut lines = Vec::new();
+        let hunks = hunks(&ops, self.context);
+        if let (Some((old_label, new_label)), false) = (&self.labels, hunks.is_empty()) {
+            lines.push(StyledString::styled(
+                self.file_header_style,
+                format!("--- {old_label}"),
+            ));
+            lines.push(StyledString::styled(
+                self.file_header_style,
+                format!("+++ {new_label}"),
+            ));
+        }
+
+        for hunk in hunks {
+            lines.push(StyledString::styled(
+                self.hunk_header_style,
+                hunk_header(&ops, hunk.clone()),
+            ));
+            let ops = &ops[hunk];
+
+            let mut i = 0;
+            while i < ops.len() {
+                if let Op::Equal(old_index, _) = ops[i] {
+                    lines.push(StyledString::styled(
+                        self.context_style,
+                        format!(" {}", old[old_index]),
+                    ));
+                    i += 1;
+                    continue;
+                }
+
+                // A run of changes: deletions and insertions until the next unchanged line.
+                let end = ops[i..]
+                    .iter()
+                    .position(|op| matches!(op, Op::Equal(..)))
+                    .map_or(ops.len(), |offset| i + offset);
+                let deleted: Vec<&str> = ops[i..end]
+                    .iter()
+                    .filter_map(|op| match op {
+                        Op::Delete(index) => Some(old[*index]),
+                        _ => None,
+                    })
+                    .collect();
+                let inserted: Vec<&str> = ops[i..end]
+                    .iter()
+                    .filter_map(|op| match op {
+                        Op::Insert(index) => Some(new[*index]),
+                        _ => None,
+                    })
+                    .collect();
+                self.push_changes(&mut lines, &deleted, &inserted);
+                i = end;
+            }
+        }
+
+        let mut rendered = StyledString::new();
+        for (i, line) in lines.into_iter().enumerate() {
+            if i > 0 {
+                rendered.push_str("\n");
+            }
+            rendered.append(line);
+        }
+        rendered
+    }
+
+    /// Render a run of deleted lines followed by inserted lines. Lines are paired up in order
+    /// for word-level emphasis.
+    fn push_changes(&self, lines: &mut Vec<StyledString>, deleted: &[&str], inserted: &[&str]) {
+        let mut deleted_lines = Vec::new();
+        let mut inserted_lines = Vec::new();
+        for i in 0..deleted.len().max(inserted.len()) {
+            match (deleted.get(i), inserted.get(i)) {
+                (Some(old), Some(new)) if self.word_diff => {
+                    let (old, new) = self.word_changes(old, new);
+                    deleted_lines.push(old);
+                    inserted_lines.push(new);
+                }
+                (old, new) => {
+                    if let Some(old) = old {
+                        deleted_lines
+                            .push(StyledString::styled(self.delete_style, format!("-{old}")));
+                    }
+                    if let Some(new) = new {
+                        inserted_lines
+                            .push(StyledString::styled(self.insert_style, format!("+{new}")));
+                    }
+                }
+            }
+        }
+        lines.extend(deleted_lines);
+        lines.extend(inserted_lines);
+    }
+
+    /// Render a changed line before and after, emphasising the words that differ. Lines with
+    /// nothing in common besides whitespace aren't emphasised, as every word would be.
+    fn word_changes(&self, old: &str, new: &str) -> (StyledString, StyledString) {
+        let old_words = words(old);
+        let new_words = words(new);
+        let ops = diff_ops(&old_words, &new_words);
+        let shared = ops.iter().any(|op| match op {
+            Op::Equal(index, _) => !old_words[*index].trim().is_empty(),
+            _ => false,
+        });
+
+        let mut old_line = StyledString::styled(self.delete_style, "-");
+        let mut new_line = StyledString::styled(self.insert_style, "+");
+        for op in ops {
+            match op {
+                Op::Equal(old_index, new_index) => {
+                    old_line.push(self.delete_style, old_words[old_index]);
+                    new_line.push(self.insert_style, new_words[new_index]);
+                }
+                Op::Delete(index) => {
+                    let style = if shared {
+                        self.delete_emphasis
+                    } else {
+                        self.delete_style
+                    };
+                    old_line.push(style, old_words[index]);
+                }
+                Op::Insert(index) => {
+                    let style = if shared {
+                        self.insert_emphasis
+                    } else {
+                        self.insert_style
+                    };
+                    new_line.push(style, new_words[index]);
+                }
+            }
+        }
+        (old_line, new_line)
+    }
+}
+
+impl Default for Diff {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Render a coloured unified diff between two texts using the default `Diff` settings.
+pub fn diff(old: &str, new: &str) -> StyledString {
+    Diff::new().diff(old, new)
+}
+
+/// Bold text on a background of the style's foreground colour, in black so it stays readable.
+fn emphasis(style: Style, fallback: Color) -> Style {
+    let mut emphasis = style.decoration(Decoration::Bold);
+    emphasis.bg = Some(style.fg.unwrap_or(StyleColor::Named(fallback)));
+    emphasis.fg = Some(StyleColor::Named(Color::Black));
+    emphasis
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Op {
+    Equal(usize, usize),
+    Delete(usize),
+    Insert(usize),
+}
+
+/// The largest LCS table, in cells, worked out in one go. Bigger inputs are split in half
+/// first, so the memory used stays linear in their length.
+const MAX_TABLE_CELLS: usize = 1 << 20;
+
+/// Work out the edits turning `old` into `new` using the longest common subsequence.
+fn diff_ops<T: PartialEq>(old: &[T], new: &[T]) -> Vec<Op> {
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let old_middle = &old[prefix..old.len() - suffix];
+    let new_middle = &new[prefix..new.len() - suffix];
+
+    let mut ops: Vec<Op> = (0..prefix).map(|i| Op::Equal(i, i)).collect();
+    lcs_ops(old_middle, new_middle, (prefix, prefix), &mut ops);
+    let old_end = old.len() - suffix;
+    let new_end = new.len() - suffix;
+    ops.extend((0..suffix).map(|k| Op::Equal(old_end + k, new_end + k)));
+    ops
+}
+
+/// Push the edits turning `old` into `new`, whose first items are at `offset` in the whole
+/// inputs. Inputs too big for one table are split with Hirschberg's algorithm: `old` is cut
+/// in half and `new` where the two halves' common subsequences add up to the longest.
+fn lcs_ops<T: PartialEq>(old: &[T], new: &[T], offset: (usize, usize), ops: &mut Vec<Op>) {
+    if old.len() < 2 || (old.len() + 1) * (new.len() + 1) <= MAX_TABLE_CELLS {
+        return table_ops(old, new, offset, ops);
+    }
+
+    let middle = old.len() / 2;
+    let forward = lcs_lengths(old[..middle].iter(), new.iter());
+    let mut backward = lcs_lengths(old[middle..].iter().rev(), new.iter().rev());
+    backward.reverse();
+    let split = (0..=new.len())
+        .max_by_key(|&j| (forward[j] + backward[j], core::cmp::Reverse(j)))
+        .unwrap_or(0);
+
+    lcs_ops(&old[..middle], &new[..split], offset, ops);
+    lcs_ops(
+        &old[middle..],
+        &new[split..],
+        (offset.0 + middle, offset.1 + split),
+        ops,
+    );
+}
+
+/// The length of the longest common subsequence of all of `old` and the first `j` items of
+/// `new`, for every `j`, keeping only one row of the table at a time.
+fn lcs_lengths<'a, T: PartialEq + 'a>(
+    old: impl Iterator<Item = &'a T>,
+    new: impl Iterator<Item = &'a T> + Clone,
+) -> Vec<u32> {
+    let mut row = vec![0u32; new.clone().count() + 1];
+    for a in old {
+        let mut diagonal = 0;
+        for (j, b) in new.clone().enumerate() {
+            let above = row[j + 1];
+            row[j + 1] = if a == b {
+                diagonal + 1
+            } else {
+                above.max(row[j])
+            };
+            diagonal = above;
+        }
+    }
+    row
+}
+
+/// Push the edits turning `old` into `new` using a full LCS table.
+fn table_ops<T: PartialEq>(old: &[T], new: &[T], offset: (usize, usize), ops: &mut Vec<Op>) {
+    // lengths[i][j] is the length of the longest common subsequence of old[i..] and new[j..].
+    let columns = new.len() + 1;
+    let mut lengths = vec![0u32; (old.len() + 1) * columns];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lengths[i * columns + j] = if old[i] == new[j] {
+                lengths[(i + 1) * columns + j + 1] + 1
+            } else {
+                lengths[(i + 1) * columns + j].max(lengths[i * columns + j + 1])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            ops.push(Op::Equal(offset.0 + i, offset.1 + j));
+            i += 1;
+            j += 1;
+        } else if j == new.len()
+            || (i < old.len() && lengths[(i + 1) * columns + j] >= lengths[i * columns + j + 1])
+        {
+            ops.push(Op::Delete(offset.0 + i));
+            i += 1;
+        } else {
+            ops.push(Op::Insert(offset.1 + j));
+            j += 1;
+        }
+    }
+}
+
+/// Group changes into ranges of `ops`, each including up to `context` unchanged lines on
+/// either side. Changes separated by fewer than `2 * context` unchanged lines share a hunk.
+fn hunks(ops: &[Op], context: usize) -> Vec<std::ops::Range<usize>> {
+    let mut hunks: Vec<std::ops::Range<usize>> = Vec::new();
+    for (i, op) in ops.iter().enumerate() {
+        if matches!(op, Op::Equal(..)) {
+            continue;
+        }
+        let start = i.saturating_sub(context);
+        let end = (i + 1 + context).min(ops.len());
+        match hunks.last_mut() {
+            Some(last) if start <= last.end => last.end = end,
+            _ => hunks.push(start..end),
+        }
+    }
+    hunks
+}
+
+fn hunk_header(ops: &[Op], hunk: std::ops::Range<usize>) -> String {
+    let old_lines = |ops: &[Op]| ops.iter().filter(|op| !matches!(op, Op::Insert(_))).count();
+    let new_lines = |ops: &[Op]| ops.iter().filter(|op| !matches!(op, Op::Delete(_))).count();
+    // A side without lines in the hunk starts at the line before it, as in GNU diff.
+    let range = |before: usize, count: usize| match count {
+        0 => format!("{before},0"),
+        _ => format!("{},{count}", before + 1),
+    };
+    format!(
+        "@@ -{} +{} @@",
+        range(old_lines(&ops[..hunk.start]), old_lines(&ops[hunk.clone()])),
+        range(new_lines(&ops[..hunk.start]), new_lines(&ops[hunk]))
+    )
+}
+
+/// Split a line into words, runs of whitespace and single punctuation characters.
+fn words(line: &str) -> Vec<&str> {
+    let mut words = Vec::new();
+    let mut start = 0;
+    let mut chars = line.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        let same_kind = |next: char| {
+            (c.is_alphanumeric() || c == '_') && (next.is_alphanumeric() || next == '_')
+                || c.is_whitespace() && next.is_whitespace()
+        };
+        match chars.peek() {
+            Some(&(_, next)) if same_kind(next) => {}
+            _ => {
+                let end = i + c.len_utf8();
+                words.push(&line[start..end]);
+                start = end;
+            }
+        }
+    }
+    words
+}
@@ -29,39 +29,120 @@ pub fn set_color_choice(choice: ColorChoice) {
 /// TRUE_COLOR_ALLOWED
 ///
 /// Choose whether RGB should be allowed or not.
-/// TRUE_COLOR_ALLOWED is automatically set using terminal settings, it can be overridden using `set_true_color_allowed()`.
-static TRUE_COLOR_ALLOWED: RwLock<bool> = RwLock::new(true);
+/// TRUE_COLOR_ALLOWED is automatically worked out from the colour level, it can be overridden using `set_true_color_allowed()`.
+static TRUE_COLOR_ALLOWED: RwLock<Option<bool>> = RwLock::new(None);
 /// Get the current `TRUE_COLOR_ALLOWED` setting.
 pub fn true_color_allowed() -> bool {
-    *TRUE_COLOR_ALLOWED.read().unwrap()
+    supported_color_level() == ColorLevel::TrueColor && true_color_override() != Some(false)
 }
-/// Set a new `TRUE_COLOR_ALLOWED` setting.
+/// Set a new `TRUE_COLOR_ALLOWED` setting. `Some(true)` assumes the terminal supports RGB,
+/// `Some(false)` makes RGB colours fall back to the closest ANSI colour and `None` goes back
+/// to detection.
 pub fn set_true_color_allowed(override_allowed: Option<bool>) {
-    let mut is_allowed = matches!(env::var("COLORTERM").as_deref(), Ok("truecolor" | "24bit"));
+    *TRUE_COLOR_ALLOWED.write().unwrap() = override_allowed;
+}
+pub(crate) fn true_color_override() -> Option<bool> {
+    *TRUE_COLOR_ALLOWED.read().unwrap()
+}
 
-    if let Some(override_val) = override_allowed {
-        is_allowed = override_val;
-    }
+/// COLOR_LEVEL
+///
+/// How many colours a terminal can show. Colours are downgraded to the closest one available
+/// at the supported level, and nothing is styled at `None`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ColorLevel {
+    None,
+    Basic16,
+    Ansi256,
+    TrueColor,
+}
+static COLOR_LEVEL_OVERRIDE: RwLock<Option<ColorLevel>> = RwLock::new(None);
+/// Get the current `COLOR_LEVEL_OVERRIDE` setting.
+pub fn color_level_override() -> Option<ColorLevel> {
+    *COLOR_LEVEL_OVERRIDE.read().unwrap()
+}
+/// Set a new `COLOR_LEVEL_OVERRIDE` setting. `None` goes back to detection.
+pub fn set_color_level_override(level: Option<ColorLevel>) {
+    *COLOR_LEVEL_OVERRIDE.write().unwrap() = level;
+}
 
-    *TRUE_COLOR_ALLOWED.write().unwrap() = is_allowed;
-    set_true_color_initialized();
+/// Work out the colour level of output written to a stream. This is `ColorLevel::None` when
+/// `should_colorize()` is `false`, and `supported_color_level()` otherwise.
+pub fn color_level(stream: Stream) -> ColorLevel {
+    if should_colorize(stream) {
+        supported_color_level()
+    } else {
+        ColorLevel::None
+    }
 }
 
-// Store whether `TRUE_COLOR_ALLOWED` has been initialized.
-static TRUE_COLOR_INITIALIZED: RwLock<bool> = RwLock::new(false);
-fn true_color_initialized() -> bool {
-    *TRUE_COLOR_INITIALIZED.read().unwrap()
+/// Get the colour level styled output is rendered at: the `COLOR_LEVEL_OVERRIDE` if set,
+/// `TrueColor` after `set_true_color_allowed(Some(true))`, and `detect_color_level()` otherwise.
+pub fn supported_color_level() -> ColorLevel {
+    if let Some(level) = color_level_override() {
+        return level;
+    }
+    match true_color_override() {
+        Some(true) => ColorLevel::TrueColor,
+        _ => detect_color_level(),
+    }
 }
-fn set_true_color_initialized() {
-    *TRUE_COLOR_INITIALIZED.write().unwrap() = true;
+
+/// Work out the colour level of the terminal from the environment, without checking whether
+/// output is connected to it.
+///
+/// - `COLORTERM=truecolor` or `24bit`, a `TERM` ending in `-direct`, Windows Terminal
+///   (`WT_SESSION`) and terminals known to support RGB through `TERM_PROGRAM` mean
+///   `TrueColor`.
+/// - Apple Terminal means `Ansi256`.
+/// - A CI system found by `detect_ci()` decides next, based on what its log viewer shows.
+/// - With the `terminfo` feature, the terminfo entry for `TERM` decides next, when there is one.
+/// - A `TERM` ending in `256color` means `Ansi256`, and other CI systems (`CI`) mean `Basic16`.
+/// - `TERM=dumb` means `None`. Anything else is assumed to support the 16 ANSI colours,
+///   except Windows consoles which support RGB.
+pub fn detect_color_level() -> ColorLevel {
+    let term = env::var("TERM").unwrap_or_default();
+    if term == "dumb" {
+        return ColorLevel::None;
+    }
+    if matches!(env::var("COLORTERM").as_deref(), Ok("truecolor" | "24bit"))
+        || term.ends_with("-direct")
+        || env::var_os("WT_SESSION").is_some()
+    {
+        return ColorLevel::TrueColor;
+    }
+    match env::var("TERM_PROGRAM").as_deref() {
+        Ok("iTerm.app" | "WezTerm" | "vscode" | "ghostty" | "Hyper") => {
+            return ColorLevel::TrueColor
+        }
+        Ok("Apple_Terminal") => return ColorLevel::Ansi256,
+        _ => {}
+    }
+    if let Some(ci) = detect_ci() {
+        return ci.color_level();
+    }
+    #[cfg(feature = "terminfo")]
+    if let Some(level) = crate::terminfo::cached_color_level(&term) {
+        return level;
+    }
+    if term.ends_with("256color") {
+        return ColorLevel::Ansi256;
+    }
+    if env::var_os("CI").is_some() {
+        return ColorLevel::Basic16;
+    }
+    if cfg!(windows) {
+        ColorLevel::TrueColor
+    } else {
+        ColorLevel::Basic16
+    }
 }
 
 /// Work out whether output should be styled or not based on terminal settings.
+///
+/// stdout and stderr are also styled when running in a CI system whose log viewer shows
+/// colours (see `detect_ci()`), even though they aren't connected to a terminal.
 pub fn should_colorize(stream: Stream) -> bool {
-    if !true_color_initialized() {
-        set_true_color_allowed(None);
-    }
-
     if color_choice() == ColorChoice::Never {
         return false;
     }
@@ -84,8 +165,11 @@ pub fn should_colorize(stream: Stream) -> bool {
     }
 
     match stream {
-        Stream::Stdout => is_tty_stdout(),
-        Stream::Stderr => is_tty_stderr(),
+        Stream::Stdout => is_tty_stdout() || detect_ci().is_some(),
+        Stream::Stderr => is_tty_stderr() || detect_ci().is_some(),
+        #[cfg(unix)]
+        Stream::Fd(fd) => is_tty_fd(fd),
+        Stream::Custom(supported) => supported,
     }
 }
 
@@ -112,6 +196,209 @@ fn is_tty_stderr() -> bool {
     windows::is_tty_stderr()
 }
 
+/// CI_SYSTEM
+///
+/// Continuous integration systems whose log viewers show ANSI colours.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CiSystem {
+    GitHubActions,
+    GitLabCi,
+    Buildkite,
+    Jenkins,
+    AzurePipelines,
+}
+
+impl CiSystem {
+    /// Get the colour level the log viewer of the CI system shows.
+    pub const fn color_level(self) -> ColorLevel {
+        match self {
+            CiSystem::GitHubActions => ColorLevel::TrueColor,
+            CiSystem::GitLabCi | CiSystem::Buildkite => ColorLevel::Ansi256,
+            CiSystem::Jenkins | CiSystem::AzurePipelines => ColorLevel::Basic16,
+        }
+    }
+}
+
+/// Work out which CI system the process is running in, from the environment variables each
+/// one documents: `GITHUB_ACTIONS`, `GITLAB_CI`, `BUILDKITE`, `JENKINS_URL` with `BUILD_ID`,
+/// and `TF_BUILD`.
+pub fn detect_ci() -> Option<CiSystem> {
+    let is_true = |name: &str| env::var(name).is_ok_and(|value| value.eq_ignore_ascii_case("true"));
+    if is_true("GITHUB_ACTIONS") {
+        Some(CiSystem::GitHubActions)
+    } else if is_true("GITLAB_CI") {
+        Some(CiSystem::GitLabCi)
+    } else if is_true("BUILDKITE") {
+        Some(CiSystem::Buildkite)
+    } else if env::var_os("JENKINS_URL").is_some() && env::var_os("BUILD_ID").is_some() {
+        Some(CiSystem::Jenkins)
+    } else if is_true("TF_BUILD") {
+        Some(CiSystem::AzurePipelines)
+    } else {
+        None
+    }
+}
+
+/// UNICODE_OVERRIDE
+///
+/// Override whether the terminal is assumed to display UTF-8, for example box drawing characters.
+static UNICODE_OVERRIDE: RwLock<Option<bool>> = RwLock::new(None);
+/// Get the current `UNICODE_OVERRIDE` setting.
+pub fn unicode_override() -> Option<bool> {
+    *UNICODE_OVERRIDE.read().unwrap()
+}
+/// Set a new `UNICODE_OVERRIDE` setting. `None` goes back to detection.
+pub fn set_unicode_override(supported: Option<bool>) {
+    *UNICODE_OVERRIDE.write().unwrap() = supported;
+}
+
+/// Work out whether the terminal can display UTF-8 based on terminal settings.
+///
+/// `TERM=dumb` means no. Otherwise the first of `LC_ALL`, `LC_CTYPE` and `LANG` that is set
+/// decides, based on whether it names a UTF-8 codeset. Windows Terminal and modern Windows
+/// consoles are assumed to support UTF-8.
+pub fn unicode_supported() -> bool {
+    if let Some(supported) = unicode_override() {
+        return supported;
+    }
+    if matches!(env::var("TERM").as_deref(), Ok("dumb")) {
+        return false;
+    }
+
+    let locale = ["LC_ALL", "LC_CTYPE", "LANG"]
+        .iter()
+        .filter_map(|name| env::var(name).ok())
+        .find(|value| !value.is_empty());
+    match locale {
+        Some(locale) => {
+            let locale = locale.to_ascii_lowercase();
+            locale.contains("utf-8") || locale.contains("utf8")
+        }
+        None => cfg!(windows),
+    }
+}
+
+/// BACKGROUND
+///
+/// Whether the terminal background is dark or light.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Background {
+    Dark,
+    Light,
+}
+static BACKGROUND_OVERRIDE: RwLock<Option<Background>> = RwLock::new(None);
+/// Get the current `BACKGROUND_OVERRIDE` setting.
+pub fn background_override() -> Option<Background> {
+    *BACKGROUND_OVERRIDE.read().unwrap()
+}
+/// Set a new `BACKGROUND_OVERRIDE` setting. `None` goes back to detection.
+pub fn set_background_override(background: Option<Background>) {
+    *BACKGROUND_OVERRIDE.write().unwrap() = background;
+}
+
+/// Work out whether the terminal background is dark or light.
+///
+/// Uses the background colour in `COLORFGBG`, which terminals such as rxvt and Konsole set to
+/// `fg;bg`. White (7) and the bright colours other than bright black count as light. Without
+/// it a dark background is assumed.
+pub fn background() -> Background {
+    if let Some(background) = background_override() {
+        return background;
+    }
+
+    let bg = env::var("COLORFGBG")
+        .ok()
+        .and_then(|value| value.rsplit(';').next()?.parse::<u8>().ok());
+    match bg {
+        Some(7 | 9..=15) => Background::Light,
+        _ => Background::Dark,
+    }
+}
+
+/// TERMINAL_SIZE
+///
+/// The number of columns and rows of a terminal.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TerminalSize {
+    pub columns: u16,
+    pub rows: u16,
+}
+static TERMINAL_SIZE_OVERRIDE: RwLock<Option<TerminalSize>> = RwLock::new(None);
+/// Get the current `TERMINAL_SIZE_OVERRIDE` setting.
+pub fn terminal_size_override() -> Option<TerminalSize> {
+    *TERMINAL_SIZE_OVERRIDE.read().unwrap()
+}
+/// Set a new `TERMINAL_SIZE_OVERRIDE` setting. While set, `terminal_size()` returns it for
+/// every stream, which keeps tests independent of the terminal they run in.
+pub fn set_terminal_size_override(size: Option<TerminalSize>) {
+    *TERMINAL_SIZE_OVERRIDE.write().unwrap() = size;
+}
+
+/// Work out the size of the terminal a stream is connected to.
+///
+/// Asks the terminal directly (`ioctl(TIOCGWINSZ)` on Unix, the console buffer on Windows) and
+/// falls back to the `COLUMNS` and `LINES` environment variables, using 24 rows when only
+/// `COLUMNS` is set. Returns `None` when the size can't be worked out.
+pub fn terminal_size(stream: Stream) -> Option<TerminalSize> {
+    if let Some(size) = terminal_size_override() {
+        return Some(size);
+    }
+
+    let size = match stream {
+        Stream::Stdout => terminal_size_stdout(),
+        Stream::Stderr => terminal_size_stderr(),
+        #[cfg(unix)]
+        Stream::Fd(fd) => terminal_size_fd(fd),
+        Stream::Custom(_) => None,
+    };
+    size.or_else(terminal_size_from_env)
+}
+
+fn terminal_size_from_env() -> Option<TerminalSize> {
+    let columns = env::var("COLUMNS").ok()?.trim().parse().ok()?;
+    let rows = env::var("LINES")
+        .ok()
+        .and_then(|lines| lines.trim().parse().ok())
+        .unwrap_or(24);
+    if columns == 0 || rows == 0 {
+        return None;
+    }
+    Some(TerminalSize { columns, rows })
+}
+
+#[cfg(unix)]
+fn terminal_size_stdout() -> Option<TerminalSize> {
+    terminal_size_fd(1)
+}
+#[cfg(unix)]
+fn terminal_size_stderr() -> Option<TerminalSize> {
+    terminal_size_fd(2)
+}
+
+#[cfg(unix)]
+fn terminal_size_fd(fd: i32) -> Option<TerminalSize> {
+    let mut size: libc::winsize = unsafe { std::mem::zeroed() };
+    if unsafe { libc::ioctl(fd, libc::TIOCGWINSZ, &mut size) } != 0 {
+        return None;
+    }
+    if size.ws_col == 0 || size.ws_row == 0 {
+        return None;
+    }
+    Some(TerminalSize {
+        columns: size.ws_col,
+        rows: size.ws_row,
+    })
+}
+
+#[cfg(windows)]
+fn terminal_size_stdout() -> Option<TerminalSize> {
+    windows::terminal_size_stdout()
+}
+#[cfg(windows)]
+fn terminal_size_stderr() -> Option<TerminalSize> {
+    windows::terminal_size_stderr()
+}
+
 /// Predefined Styles
 ///
 /// Choose the styles for predefined success, warning, error, info, and debug styles.
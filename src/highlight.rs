@@ -0,0 +1,517 @@
+//! Apply styles to the parts of text matching literal substrings, simple patterns or custom
+//! matchers.
+//!
+//! ```rust
+//! use rustyhues::highlight::{Highlighter, Pattern};
+//! use rustyhues::{Color, Decoration, Style};
+//!
+//! let highlighter = Highlighter::new()
+//!     .pattern(Pattern::new(r"req-\d+").unwrap(), Style::new().fg(Color::Magenta))
+//!     .priority(1)
+//!     .pattern(Pattern::new(r"\b\d+\b").unwrap(), Style::new().fg(Color::Cyan))
+//!     .literal_ignore_case("timeout", Style::new().decoration(Decoration::Bold));
+//! println!("{}", highlighter.highlight("req-42 failed after 3 retries: Timeout"));
+//! ```
+use crate::{style::Style, styled::StyledString};
+use core::{fmt, ops::Range};
+use std::sync::Arc;
+
+/// Pattern Error
+///
+/// The reasons a `Pattern` can't be parsed. Positions are byte offsets into the pattern.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PatternError {
+    /// The pattern is empty.
+    Empty,
+    /// A `[` is never closed with `]`.
+    UnclosedClass { position: usize },
+    /// A `\` is the last character of the pattern.
+    TrailingEscape { position: usize },
+    /// `*`, `+` or `?` doesn't follow anything that can be repeated.
+    NothingToRepeat { position: usize },
+}
+
+impl fmt::Display for PatternError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PatternError::Empty => write!(f, "pattern is empty"),
+            PatternError::UnclosedClass { position } => {
+                write!(
+                    f,
+                    "character class at position {position} is missing a closing `]`"
+                )
+            }
+            PatternError::TrailingEscape { position } => {
+                write!(f, "escape at position {position} has nothing to escape")
+            }
+            PatternError::NothingToRepeat { position } => {
+                write!(f, "repetition at position {position} has nothing to repeat")
+            }
+        }
+    }
+}
+
+impl std::error::Error for PatternError {}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum ClassItem {
+    Char(char),
+    Range(char, char),
+    Digit,
+    Word,
+    Space,
+}
+
+impl ClassItem {
+    fn matches(&self, c: char) -> bool {
+        match *self {
+            ClassItem::Char(expected) => c == expected,
+            ClassItem::Range(start, end) => (start..=end).contains(&c),
+            ClassItem::Digit => c.is_ascii_digit(),
+            ClassItem::Word => is_word(c),
+            ClassItem::Space => c.is_whitespace(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Atom {
+    Any,
+    Class {
+        negated: bool,
+        items: Vec<ClassItem>,
+    },
+    Boundary,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Repeat {
+    One,
+    Optional,
+    Any,
+    Many,
+}
+
+/// Pattern
+///
+/// A simple pattern, similar to a regular expression without groups or alternation:
+/// - `.` matches any character and `\d`, `\w` and `\s` match digits, word characters and
+///   whitespace. Upper case `\D`, `\W` and `\S` match everything else.
+/// - `[abc]`, `[a-z0-9_]` and `[^0-9]` match sets of characters.
+/// - `*`, `+` and `?` repeat the previous item zero or more, one or more or zero or one times,
+///   matching as much as possible.
+/// - `\b` matches the boundary between a word character and anything else.
+/// - `\` before any other character matches it literally, for example `\.`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Pattern {
+    items: Vec<(Atom, Repeat)>,
+}
+
+impl Pattern {
+    /// Parse a Pattern.
+    pub fn new(pattern: &str) -> Result<Self, PatternError> {
+        let mut items: Vec<(Atom, Repeat)> = Vec::new();
+        let mut chars = pattern.char_indices().peekable();
+        while let Some((position, c)) = chars.next() {
+            let atom = match c {
+                '.' => Atom::Any,
+                '\\' => match chars.next() {
+                    Some((_, 'b')) => Atom::Boundary,
+                    Some((_, escaped)) => escape_atom(escaped),
+                    None => return Err(PatternError::TrailingEscape { position }),
+                },
+                '[' => {
+                    let negated = chars.next_if(|&(_, c)| c == '^').is_some();
+                    let mut class = Vec::new();
+                    loop {
+                        let item = match chars.next() {
+                            Some((_, ']')) if !class.is_empty() => break,
+                            Some((_, '\\')) => match chars.next() {
+                                Some((_, escaped)) => escape_class_item(escaped),
+                                None => return Err(PatternError::UnclosedClass { position }),
+                            },
+                            Some((_, c)) => ClassItem::Char(c),
+                            None => return Err(PatternError::UnclosedClass { position }),
+                        };
+                        let range_end = match (&item, chars.peek()) {
+                            (ClassItem::Char(_), Some(&(_, '-'))) => {
+                                let mut lookahead = chars.clone();
+                                lookahead.next();
+                                match lookahead.next() {
+                                    Some((_, end)) if end != ']' => {
+                                        chars = lookahead;
+                                        Some(end)
+                                    }
+                                    _ => None,
+                                }
+                            }
+                            _ => None,
+                        };
+                        class.push(match (item, range_end) {
+                            (ClassItem::Char(start), Some(end)) => ClassItem::Range(start, end),
+                            (item, _) => item,
+                        });
+                    }
+                    Atom::Class {
+                        negated,
+                        items: class,
+                    }
+                }
+                '*' | '+' | '?' => {
+                    let repeat = match c {
+                        '*' => Repeat::Any,
+                        '+' => Repeat::Many,
+                        _ => Repeat::Optional,
+                    };
+                    match items.last_mut() {
+                        Some((atom, last @ Repeat::One)) if *atom != Atom::Boundary => {
+                            *last = repeat;
+                        }
+                        _ => return Err(PatternError::NothingToRepeat { position }),
+                    }
+                    continue;
+                }
+                c => Atom::Class {
+                    negated: false,
+                    items: vec![ClassItem::Char(c)],
+                },
+            };
+            items.push((atom, Repeat::One));
+        }
+        if items.is_empty() {
+            return Err(PatternError::Empty);
+        }
+        Ok(Self { items })
+    }
+
+    /// Find the byte ranges of all non-overlapping matches, from left to right. Empty matches
+    /// are skipped.
+    pub fn find_all(&self, text: &str) -> Vec<Range<usize>> {
+        let chars: Vec<(usize, char)> = text.char_indices().collect();
+        let offset = |index: usize| chars.get(index).map_or(text.len(), |(offset, _)| *offset);
+        let ends = self.match_ends(&chars);
+        let mut matches = Vec::new();
+        let mut start = 0;
+        while start < chars.len() {
+            match ends[start] {
+                Some(end) if end > start => {
+                    matches.push(offset(start)..offset(end));
+                    start = end;
+                }
+                _ => start += 1,
+            }
+        }
+        matches
+    }
+
+    /// Work out, for every character position, where a match starting there ends. Repeats
+    /// match as much as possible and give characters back only when the rest of the pattern
+    /// needs them, like a backtracking matcher would, but the items are matched from last to
+    /// first so every (item, position) pair is only worked out once.
+    fn match_ends(&self, chars: &[(usize, char)]) -> Vec<Option<usize>> {
+        let len = chars.len();
+        // Where the rest of the pattern, after the current item, ends when it starts at each
+        // position.
+        let mut rest: Vec<Option<usize>> = (0..=len).map(Some).collect();
+        for (atom, repeat) in self.items.iter().rev() {
+            let matches = |position: usize| {
+                chars
+                    .get(position)
+                    .is_some_and(|(_, c)| atom_matches(atom, *c))
+            };
+            let mut ends = vec![None; len + 1];
+            if *atom == Atom::Boundary {
+                for (position, end) in ends.iter_mut().enumerate() {
+                    let before = position.checked_sub(1).is_some_and(|i| is_word(chars[i].1));
+                    let after = chars.get(position).is_some_and(|(_, c)| is_word(*c));
+                    *end = if before != after {
+                        rest[position]
+                    } else {
+                        None
+                    };
+                }
+            } else if matches!(repeat, Repeat::Any | Repeat::Many) {
+                // Taking one more character is tried before stopping here.
+                let mut any = vec![None; len + 2];
+                for position in (0..=len).rev() {
+                    let longer = if matches(position) {
+                        any[position + 1]
+                    } else {
+                        None
+                    };
+                    any[position] = longer.or(rest[position]);
+                }
+                for (position, end) in ends.iter_mut().enumerate() {
+                    *end = match repeat {
+                        Repeat::Many if matches(position) => any[position + 1],
+                        Repeat::Many => None,
+                        _ => any[position],
+                    };
+                }
+            } else {
+                for (position, end) in ends.iter_mut().enumerate() {
+                    let taken = if matches(position) {
+                        rest[position + 1]
+                    } else {
+                        None
+                    };
+                    *end = match repeat {
+                        Repeat::Optional => taken.or(rest[position]),
+                        _ => taken,
+                    };
+                }
+            }
+            rest = ends;
+        }
+        rest
+    }
+}
+
+fn is_word(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+fn atom_matches(atom: &Atom, c: char) -> bool {
+    match atom {
+        Atom::Any => true,
+        Atom::Class { negated, items } => items.iter().any(|item| item.matches(c)) != *negated,
+        Atom::Boundary => false,
+    }
+}
+
+fn escape_class_item(escaped: char) -> ClassItem {
+    match escaped {
+        'd' => ClassItem::Digit,
+        'w' => ClassItem::Word,
+        's' => ClassItem::Space,
+        c => ClassItem::Char(c),
+    }
+}
+
+fn escape_atom(escaped: char) -> Atom {
+    let (negated, item) = match escaped {
+        'D' => (true, ClassItem::Digit),
+        'W' => (true, ClassItem::Word),
+        'S' => (true, ClassItem::Space),
+        c => (false, escape_class_item(c)),
+    };
+    Atom::Class {
+        negated,
+        items: vec![item],
+    }
+}
+
+type MatchFn = Arc<dyn Fn(&str) -> Vec<Range<usize>> + Send + Sync>;
+
+#[derive(Clone)]
+enum Matcher {
+    Literal { text: String, ignore_case: bool },
+    Pattern(Pattern),
+    Custom(MatchFn),
+}
+
+impl fmt::Debug for Matcher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Matcher::Literal { text, ignore_case } => f
+                .debug_struct("Literal")
+                .field("text", text)
+                .field("ignore_case", ignore_case)
+                .finish(),
+            Matcher::Pattern(pattern) => f.debug_tuple("Pattern").field(pattern).finish(),
+            Matcher::Custom(_) => f.write_str("Custom"),
+        }
+    }
+}
+
+impl Matcher {
+    fn find_all(&self, text: &str) -> Vec<Range<usize>> {
+        match self {
+            Matcher::Literal { text: needle, .. } if needle.is_empty() => Vec::new(),
+            Matcher::Literal {
+                text: needle,
+                ignore_case: false,
+            } => text
+                .match_indices(needle.as_str())
+                .map(|(start, found)| start..start + found.len())
+                .collect(),
+            Matcher::Literal {
+                text: needle,
+                ignore_case: true,
+            } => {
+                let mut matches = Vec::new();
+                let mut start = 0;
+                while start + needle.len() <= text.len() {
+                    let candidate = text.get(start..start + needle.len());
+                    if candidate.is_some_and(|candidate| candidate.eq_ignore_ascii_case(needle)) {
+                        matches.push(start..start + needle.len());
+                        start += needle.len();
+                    } else {
+                        start += 1;
+                    }
+                }
+                matches
+            }
+            Matcher::Pattern(pattern) => pattern.find_all(text),
+            Matcher::Custom(find) => find(text)
+                .into_iter()
+                .filter(|range| {
+                    range.start < range.end
+                        && text.is_char_boundary(range.start)
+                        && range.end <= text.len()
+                        && text.is_char_boundary(range.end)
+                })
+                .collect(),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Rule {
+    matcher: Matcher,
+    style: Style,
+    priority: i32,
+}
+
+/// Highlighter
+///
+/// A list of rules, each applying a `Style` to the parts of text it matches.
+///
+/// When matches overlap, the one from the rule with the highest priority is kept whole and the
+/// others are dropped. Rules have priority 0 unless changed with `priority()`, and between
+/// rules of the same priority the one added first wins.
+#[derive(Clone, Debug, Default)]
+pub struct Highlighter {
+    rules: Vec<Rule>,
+}
+
+impl Highlighter {
+    /// Create a new Highlighter without any rules.
+    pub fn new() -> Self {
+        Self { rules: Vec::new() }
+    }
+
+    fn rule(mut self, matcher: Matcher, style: Style) -> Self {
+        self.rules.push(Rule {
+            matcher,
+            style,
+            priority: 0,
+        });
+        self
+    }
+
+    /// Highlight every occurrence of a substring.
+    pub fn literal(self, text: impl Into<String>, style: Style) -> Self {
+        let text = text.into();
+        self.rule(
+            Matcher::Literal {
+                text,
+                ignore_case: false,
+            },
+            style,
+        )
+    }
+
+    /// Highlight every occurrence of a substring, ignoring ASCII case.
+    pub fn literal_ignore_case(self, text: impl Into<String>, style: Style) -> Self {
+        let text = text.into();
+        self.rule(
+            Matcher::Literal {
+                text,
+                ignore_case: true,
+            },
+            style,
+        )
+    }
+
+    /// Highlight every match of a `Pattern`.
+    pub fn pattern(self, pattern: Pattern, style: Style) -> Self {
+        self.rule(Matcher::Pattern(pattern), style)
+    }
+
+    /// Highlight the byte ranges returned by a function. Ranges that are empty, out of bounds
+    /// or not on character boundaries are ignored.
+    pub fn matcher<F>(self, find: F, style: Style) -> Self
+    where
+        F: Fn(&str) -> Vec<Range<usize>> + Send + Sync + 'static,
+    {
+        self.rule(Matcher::Custom(Arc::new(find)), style)
+    }
+
+    /// Set the priority of the rule added last.
+    pub fn priority(mut self, priority: i32) -> Self {
+        if let Some(rule) = self.rules.last_mut() {
+            rule.priority = priority;
+        }
+        self
+    }
+
+    /// Find the matches to highlight, as byte ranges with the style of their rule, sorted by
+    /// position.
+    pub fn matches(&self, text: &str) -> Vec<(Range<usize>, Style)> {
+        let mut candidates: Vec<(i32, usize, Range<usize>, Style)> = self
+            .rules
+            .iter()
+            .enumerate()
+            .flat_map(|(index, rule)| {
+                rule.matcher
+                    .find_all(text)
+                    .into_iter()
+                    .map(move |range| (rule.priority, index, range, rule.style))
+            })
+            .collect();
+        candidates.sort_by(|a, b| {
+            b.0.cmp(&a.0)
+                .then(a.1.cmp(&b.1))
+                .then(a.2.start.cmp(&b.2.start))
+        });
+
+        let mut accepted: Vec<(Range<usize>, Style)> = Vec::new();
+        for (_, _, range, style) in candidates {
+            let overlaps = accepted
+                .iter()
+                .any(|(other, _)| range.start < other.end && other.start < range.end);
+            if !overlaps {
+                accepted.push((range, style));
+            }
+        }
+        accepted.sort_by_key(|(range, _)| range.start);
+        accepted
+    }
+
+    /// Highlight plain text.
+    pub fn highlight(&self, text: &str) -> StyledString {
+        self.highlight_styled(&StyledString::from(text))
+    }
+
+    /// Highlight styled text. Matches are found in the plain text, and rule styles are layered
+    /// on top of the existing styles: their colours replace existing ones and their decorations
+    /// are added.
+    pub fn highlight_styled(&self, text: &StyledString) -> StyledString {
+        let matches = self.matches(&text.plain_text());
+        let mut highlighted = StyledString::new();
+        let mut matches = matches.iter().peekable();
+        let mut offset = 0;
+        for span in text.spans() {
+            let span_end = offset + span.text.len();
+            let mut position = offset;
+            while position < span_end {
+                while matches
+                    .next_if(|(range, _)| range.end <= position)
+                    .is_some()
+                {}
+                let (end, style) = match matches.peek() {
+                    Some((range, style)) if range.start <= position => {
+                        (range.end.min(span_end), span.style.patch(*style))
+                    }
+                    Some((range, _)) => (range.start.min(span_end), span.style),
+                    None => (span_end, span.style),
+                };
+                highlighted.push(style, &span.text[position - offset..end - offset]);
+                position = end;
+            }
+            offset = span_end;
+        }
+        highlighted
+    }
+}
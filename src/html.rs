@@ -0,0 +1,285 @@
+//! Convert ANSI styled text to HTML.
+//!
+//! ```rust
+//! use rustyhues::html::{Html, HtmlMode};
+//!
+//! let html = Html::new().mode(HtmlMode::Classes).render("\x1b[31merror\x1b[0m: <none>");
+//! assert_eq!(
+//!     html,
+//!     "<pre class=\"ansi\"><span class=\"ansi-fg-red\">error</span>: &lt;none&gt;</pre>"
+//! );
+//! ```
+use crate::{
+    ansi::parse_ansi,
+    style::{Color, Decoration, Palette, Style, StyleColor, ANSI_COLORS, DECORATIONS},
+    styled::StyledString,
+};
+
+/// Html Mode
+///
+/// Choose whether styles are written as inline `style=` attributes or as CSS classes.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum HtmlMode {
+    #[default]
+    Inline,
+    Classes,
+}
+
+/// Html
+///
+/// Converter from ANSI styled text to HTML.
+///
+/// In `HtmlMode::Classes` the 16 ANSI colours and decorations become classes (see
+/// `stylesheet()`), while 256 palette and RGB colours, which have no class, are written inline.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Html {
+    mode: HtmlMode,
+    palette: Palette,
+    class_prefix: String,
+    pre: bool,
+}
+
+impl Html {
+    /// Create a new Html converter using inline styles and the default palette.
+    pub fn new() -> Self {
+        Self {
+            mode: HtmlMode::Inline,
+            palette: Palette::default(),
+            class_prefix: "ansi".to_string(),
+            pre: true,
+        }
+    }
+
+    /// Set whether styles are written inline or as classes.
+    pub fn mode(mut self, mode: HtmlMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Set the palette used for the 16 ANSI colours and the 256 colour palette.
+    pub fn palette(mut self, palette: Palette) -> Self {
+        self.palette = palette;
+        self
+    }
+
+    /// Set the prefix of generated class names. Defaults to `ansi`. Characters other than
+    /// ASCII letters, digits, `_` and `-` are replaced with `-`, so the prefix is safe to use
+    /// in attributes and CSS selectors.
+    pub fn class_prefix(mut self, prefix: &str) -> Self {
+        self.class_prefix = prefix
+            .chars()
+            .map(|c| match c {
+                'a'..='z' | 'A'..='Z' | '0'..='9' | '_' | '-' => c,
+                _ => '-',
+            })
+            .collect();
+        self
+    }
+
+    /// Set whether output is wrapped in a `<pre>` element. Defaults to `true`.
+    pub fn pre(mut self, pre: bool) -> Self {
+        self.pre = pre;
+        self
+    }
+
+    /// Convert text containing ANSI escape sequences to HTML.
+    pub fn render(&self, text: &str) -> String {
+        self.render_styled(&parse_ansi(text))
+    }
+
+    /// Convert a `StyledString` to HTML.
+    pub fn render_styled(&self, styled: &StyledString) -> String {
+        let mut html = String::new();
+        if self.pre {
+            match self.mode {
+                HtmlMode::Inline => html.push_str(&format!(
+                    "<pre style=\"color:{};background-color:{}\">",
+                    self.palette.foreground.to_hex(),
+                    self.palette.background.to_hex()
+                )),
+                HtmlMode::Classes => {
+                    html.push_str(&format!("<pre class=\"{}\">", self.class_prefix))
+                }
+            }
+        }
+
+        for span in styled.spans() {
+            let (classes, declarations) = self.attributes(&span.style);
+            if classes.is_empty() && declarations.is_empty() {
+                html.push_str(&escape(&span.text));
+                continue;
+            }
+            html.push_str("<span");
+            if !classes.is_empty() {
+                html.push_str(&format!(" class=\"{}\"", classes.join(" ")));
+            }
+            if !declarations.is_empty() {
+                html.push_str(&format!(" style=\"{}\"", declarations.join(";")));
+            }
+            html.push('>');
+            html.push_str(&escape(&span.text));
+            html.push_str("</span>");
+        }
+
+        if self.pre {
+            html.push_str("</pre>");
+        }
+        html
+    }
+
+    /// Get the CSS rules for the classes used in `HtmlMode::Classes`.
+    pub fn stylesheet(&self) -> String {
+        let prefix = &self.class_prefix;
+        let mut css = format!(
+            ".{prefix} {{ color: {}; background-color: {}; }}\n",
+            self.palette.foreground.to_hex(),
+            self.palette.background.to_hex()
+        );
+        for color in ANSI_COLORS {
+            let hex = self.palette.color(color).to_hex();
+            let name = color_class(color);
+            css.push_str(&format!(".{prefix}-fg-{name} {{ color: {hex}; }}\n"));
+            css.push_str(&format!(
+                ".{prefix}-bg-{name} {{ background-color: {hex}; }}\n"
+            ));
+        }
+        // Inverted colours are written as swapped colours rather than a class.
+        for decoration in DECORATIONS
+            .into_iter()
+            .filter(|other| *other != Decoration::Invert)
+        {
+            css.push_str(&format!(
+                ".{prefix}-{} {{ {}; }}\n",
+                decoration_class(decoration),
+                decoration_declaration(decoration).replace(':', ": ")
+            ));
+        }
+        css
+    }
+
+    fn attributes(&self, style: &Style) -> (Vec<String>, Vec<String>) {
+        let mut classes = Vec::new();
+        let mut declarations = Vec::new();
+
+        let (fg, bg) = if style.has_decoration(Decoration::Invert) {
+            (
+                Some(style.bg.map_or(Side::Background, Side::Color)),
+                Some(style.fg.map_or(Side::Foreground, Side::Color)),
+            )
+        } else {
+            (style.fg.map(Side::Color), style.bg.map(Side::Color))
+        };
+
+        for (side, property, class) in [(fg, "color", "fg"), (bg, "background-color", "bg")] {
+            let rgb = match side {
+                None => continue,
+                Some(Side::Color(StyleColor::Named(color))) if self.mode == HtmlMode::Classes => {
+                    classes.push(format!(
+                        "{}-{class}-{}",
+                        self.class_prefix,
+                        color_class(color)
+                    ));
+                    continue;
+                }
+                Some(Side::Color(color)) => self.palette.resolve(color),
+                Some(Side::Foreground) => self.palette.foreground,
+                Some(Side::Background) => self.palette.background,
+            };
+            declarations.push(format!("{property}:{}", rgb.to_hex()));
+        }
+
+        for decoration in style.decorations() {
+            if decoration == Decoration::Invert {
+                continue;
+            }
+            match self.mode {
+                HtmlMode::Inline => {
+                    declarations.push(decoration_declaration(decoration).to_string())
+                }
+                HtmlMode::Classes => classes.push(format!(
+                    "{}-{}",
+                    self.class_prefix,
+                    decoration_class(decoration)
+                )),
+            }
+        }
+
+        (classes, declarations)
+    }
+}
+
+impl Default for Html {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// One side of an inverted style, which may fall back to the palette defaults.
+#[derive(Clone, Copy)]
+enum Side {
+    Color(StyleColor),
+    Foreground,
+    Background,
+}
+
+fn color_class(color: Color) -> &'static str {
+    match color {
+        Color::Black => "black",
+        Color::Red => "red",
+        Color::Green => "green",
+        Color::Yellow => "yellow",
+        Color::Blue => "blue",
+        Color::Magenta => "magenta",
+        Color::Cyan => "cyan",
+        Color::White => "white",
+        Color::BrightBlack => "bright-black",
+        Color::BrightRed => "bright-red",
+        Color::BrightGreen => "bright-green",
+        Color::BrightYellow => "bright-yellow",
+        Color::BrightBlue => "bright-blue",
+        Color::BrightMagenta => "bright-magenta",
+        Color::BrightCyan => "bright-cyan",
+        Color::BrightWhite => "bright-white",
+    }
+}
+
+fn decoration_class(decoration: Decoration) -> &'static str {
+    match decoration {
+        Decoration::Bold => "bold",
+        Decoration::Dim => "dim",
+        Decoration::Italic => "italic",
+        Decoration::Underline => "underline",
+        Decoration::Invert => "invert",
+    }
+}
+
+fn decoration_declaration(decoration: Decoration) -> &'static str {
+    match decoration {
+        Decoration::Bold => "font-weight:bold",
+        Decoration::Dim => "opacity:0.7",
+        Decoration::Italic => "font-style:italic",
+        Decoration::Underline => "text-decoration:underline",
+        Decoration::Invert => "",
+    }
+}
+
+/// Convert text containing ANSI escape sequences to HTML using inline styles.
+pub fn to_html(text: &str) -> String {
+    Html::new().render(text)
+}
+
+/// Escape text for use in HTML content or attribute values.
+pub fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -0,0 +1,109 @@
+use crate::{
+    env::{background, Background},
+    style::{rgb, Color, ANSI_COLORS, RGB},
+};
+use core::hash::{Hash, Hasher};
+
+/// Twelve hues spaced around the colour wheel, tuned to similar perceived lightness so they
+/// all read well on a dark background.
+const DARK_BACKGROUND_COLORS: [RGB; 12] = [
+    rgb(240, 113, 120),
+    rgb(240, 150, 80),
+    rgb(222, 190, 70),
+    rgb(170, 210, 80),
+    rgb(100, 205, 120),
+    rgb(70, 205, 175),
+    rgb(80, 195, 230),
+    rgb(110, 160, 245),
+    rgb(150, 140, 250),
+    rgb(190, 130, 240),
+    rgb(230, 120, 210),
+    rgb(240, 120, 165),
+];
+
+/// The same hues, darkened so they read well on a light background.
+const LIGHT_BACKGROUND_COLORS: [RGB; 12] = [
+    rgb(190, 30, 45),
+    rgb(180, 85, 0),
+    rgb(140, 110, 0),
+    rgb(90, 125, 0),
+    rgb(20, 130, 50),
+    rgb(0, 125, 110),
+    rgb(0, 115, 150),
+    rgb(30, 90, 200),
+    rgb(80, 70, 200),
+    rgb(120, 50, 180),
+    rgb(160, 30, 140),
+    rgb(185, 30, 95),
+];
+
+/// The closest of the six ANSI hues to each entry above.
+const ANSI_HUES: [Color; 12] = [
+    Color::Red,
+    Color::Yellow,
+    Color::Yellow,
+    Color::Green,
+    Color::Green,
+    Color::Cyan,
+    Color::Cyan,
+    Color::Blue,
+    Color::Blue,
+    Color::Magenta,
+    Color::Magenta,
+    Color::Red,
+];
+
+/// 64-bit FNV-1a, used instead of `DefaultHasher` whose output may change between releases.
+struct Fnv1a(u64);
+
+impl Default for Fnv1a {
+    fn default() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Hasher for Fnv1a {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= *byte as u64;
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+}
+
+fn hue_index(key: &impl Hash) -> usize {
+    let mut hasher = Fnv1a::default();
+    key.hash(&mut hasher);
+    let hash = hasher.finish();
+    ((hash ^ (hash >> 32)) % 12) as usize
+}
+
+/// Pick a stable colour for a key, for example to prefix the output of parallel jobs, threads
+/// or hosts. The same key always gets the same colour, and the palette's lightness follows
+/// `env::background()` so the colour stays readable.
+pub fn color_for(key: &impl Hash) -> RGB {
+    let index = hue_index(key);
+    match background() {
+        Background::Dark => DARK_BACKGROUND_COLORS[index],
+        Background::Light => LIGHT_BACKGROUND_COLORS[index],
+    }
+}
+
+/// The 256 colour palette entry closest to `color_for()`.
+pub fn color_for_fixed(key: &impl Hash) -> u8 {
+    color_for(key).closest_fixed()
+}
+
+/// The ANSI colour with the same hue as `color_for()`. The bright variants are used on a dark
+/// background and the normal ones on a light background.
+pub fn color_for_ansi(key: &impl Hash) -> Color {
+    let color = ANSI_HUES[hue_index(key)];
+    match background() {
+        Background::Dark => ANSI_COLORS[color.index() as usize + 8],
+        Background::Light => color,
+    }
+}
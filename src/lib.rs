@@ -4,30 +4,57 @@
 //! use rustyhues::*;
 //! println!("{}", "Hello".blue().bold());
 //! ```
+mod ansi;
+mod border;
+pub mod diff;
 pub mod env;
+pub mod highlight;
+pub mod html;
+mod key_color;
+mod markup;
 mod output;
 mod paint;
+pub mod panel;
+pub mod pretty;
+pub mod progress;
+pub mod scale;
 mod stream;
 mod style;
+mod styled;
+pub mod svg;
+pub mod table;
+#[cfg(feature = "terminfo")]
+pub mod terminfo;
+pub mod tree;
+mod truncate;
 #[cfg(windows)]
 mod windows;
+mod wrap;
 
+pub use ansi::{parse_ansi, strip_ansi, visible_width};
+pub use border::BorderStyle;
 use env::{predefined_style, PredefinedStyleType};
-pub use paint::Paint;
+pub use key_color::{color_for, color_for_ansi, color_for_fixed};
+pub use markup::{escape_markup, markup, MarkupError};
+pub use paint::{Paint, SelectArm};
+pub use stream::Stream;
 pub use style::{
-    Color, Comparison, Decoration, COLOR_BG_BLACK_CODE, COLOR_BG_BLUE_CODE,
-    COLOR_BG_BRIGHT_BLACK_CODE, COLOR_BG_BRIGHT_BLUE_CODE, COLOR_BG_BRIGHT_CYAN_CODE,
-    COLOR_BG_BRIGHT_GREEN_CODE, COLOR_BG_BRIGHT_MAGENTA_CODE, COLOR_BG_BRIGHT_RED_CODE,
-    COLOR_BG_BRIGHT_WHITE_CODE, COLOR_BG_BRIGHT_YELLOW_CODE, COLOR_BG_CYAN_CODE,
-    COLOR_BG_GREEN_CODE, COLOR_BG_MAGENTA_CODE, COLOR_BG_RED_CODE, COLOR_BG_RGB,
-    COLOR_BG_WHITE_CODE, COLOR_BG_YELLOW_CODE, COLOR_BLACK_CODE, COLOR_BLUE_CODE,
-    COLOR_BRIGHT_BLACK_CODE, COLOR_BRIGHT_BLUE_CODE, COLOR_BRIGHT_CYAN_CODE,
+    AdaptiveColor, Color, Comparison, Decoration, Palette, Style, StyleColor, COLOR_BG_BLACK_CODE,
+    COLOR_BG_BLUE_CODE, COLOR_BG_BRIGHT_BLACK_CODE, COLOR_BG_BRIGHT_BLUE_CODE,
+    COLOR_BG_BRIGHT_CYAN_CODE, COLOR_BG_BRIGHT_GREEN_CODE, COLOR_BG_BRIGHT_MAGENTA_CODE,
+    COLOR_BG_BRIGHT_RED_CODE, COLOR_BG_BRIGHT_WHITE_CODE, COLOR_BG_BRIGHT_YELLOW_CODE,
+    COLOR_BG_CYAN_CODE, COLOR_BG_FIXED, COLOR_BG_GREEN_CODE, COLOR_BG_MAGENTA_CODE,
+    COLOR_BG_RED_CODE, COLOR_BG_RGB, COLOR_BG_WHITE_CODE, COLOR_BG_YELLOW_CODE, COLOR_BLACK_CODE,
+    COLOR_BLUE_CODE, COLOR_BRIGHT_BLACK_CODE, COLOR_BRIGHT_BLUE_CODE, COLOR_BRIGHT_CYAN_CODE,
     COLOR_BRIGHT_GREEN_CODE, COLOR_BRIGHT_MAGENTA_CODE, COLOR_BRIGHT_RED_CODE,
-    COLOR_BRIGHT_WHITE_CODE, COLOR_BRIGHT_YELLOW_CODE, COLOR_CYAN_CODE, COLOR_GREEN_CODE,
-    COLOR_MAGENTA_CODE, COLOR_RED_CODE, COLOR_RGB, COLOR_WHITE_CODE, COLOR_YELLOW_CODE,
-    DECORATION_BOLD_CODE, DECORATION_DIM_CODE, DECORATION_INVERT_CODE, DECORATION_ITALIC_CODE,
-    DECORATION_UNDERLINE_CODE, RGB,
+    COLOR_BRIGHT_WHITE_CODE, COLOR_BRIGHT_YELLOW_CODE, COLOR_CYAN_CODE, COLOR_FIXED,
+    COLOR_GREEN_CODE, COLOR_MAGENTA_CODE, COLOR_RED_CODE, COLOR_RGB, COLOR_WHITE_CODE,
+    COLOR_YELLOW_CODE, DECORATION_BOLD_CODE, DECORATION_DIM_CODE, DECORATION_INVERT_CODE,
+    DECORATION_ITALIC_CODE, DECORATION_UNDERLINE_CODE, RGB,
 };
+pub use styled::{Span, StyledString};
+pub use truncate::{truncate, truncate_ansi, TruncateOptions, TruncatePosition};
+pub use wrap::{wrap, wrap_ansi, WrapOptions};
 
 /// Stylize trait.
 ///
@@ -43,6 +70,23 @@ pub trait Stylize: Sized {
         Paint::new_stderr(self)
     }
 
+    /// Create a new `Paint` context that writes to any `Stream`.
+    fn paint_for(self, stream: Stream) -> Paint<Self> {
+        Paint::new_for_stream(self, stream)
+    }
+
+    /// Create a new lazy `Paint` context that writes to std_out, deciding whether to style
+    /// the content when it is displayed.
+    fn paint_lazy(self) -> Paint<Self> {
+        Paint::lazy_stdout(self)
+    }
+
+    /// Create a new lazy `Paint` context that writes to std_err, deciding whether to style
+    /// the content when it is displayed.
+    fn paint_err_lazy(self) -> Paint<Self> {
+        Paint::lazy_stderr(self)
+    }
+
     /// Set should reset
     fn should_reset(self, reset: bool) -> Paint<Self> {
         self.paint().should_reset(reset)
@@ -67,7 +111,6 @@ pub trait Stylize: Sized {
     /// For example: FFFFFF
     fn hex(self, hex: &str) -> Paint<Self> {
         if let Some(rgb) = RGB::new_from_hex(hex) {
-            
             self.paint().set_rgb(rgb)
         } else {
             self.paint()
@@ -78,13 +121,27 @@ pub trait Stylize: Sized {
     /// For example: 333333
     fn bg_hex(self, hex: &str) -> Paint<Self> {
         if let Some(rgb) = RGB::new_from_hex(hex) {
-            
             self.paint().set_bg_rgb(rgb)
         } else {
             self.paint()
         }
     }
 
+    /// Create a new std_out `Paint` context and colour content using an entry of the 256 colour palette.
+    fn fixed(self, index: u8) -> Paint<Self> {
+        self.paint().fixed(index)
+    }
+
+    /// Create a new std_out `Paint` context and colour the background of content using an entry of the 256 colour palette.
+    fn bg_fixed(self, index: u8) -> Paint<Self> {
+        self.paint().bg_fixed(index)
+    }
+
+    /// Create a new std_out `Paint` context and apply all colours and decorations of a `Style`.
+    fn style(self, style: Style) -> Paint<Self> {
+        self.paint().style(style)
+    }
+
     /// Create a new std_out `Paint` context and colour content ANSI Black.
     fn black(self) -> Paint<Self> {
         self.paint().fg(Color::Black)
@@ -300,6 +357,51 @@ pub trait Stylize: Sized {
         self.paint().set_is_not(Comparison::Not, None)
     }
 
+    /// Set an is comparison with a condition checked every time the content is displayed
+    fn when<F>(self, condition: F) -> Paint<Self>
+    where
+        F: Fn() -> bool + Send + Sync + 'static,
+    {
+        self.paint().when(condition)
+    }
+
+    /// Set a not comparison with a condition checked every time the content is displayed
+    fn when_not<F>(self, condition: F) -> Paint<Self>
+    where
+        F: Fn() -> bool + Send + Sync + 'static,
+    {
+        self.paint().when_not(condition)
+    }
+
+    /// Set an is comparison with a predicate on the content, checked every time it is displayed
+    fn when_value<F>(self, predicate: F) -> Paint<Self>
+    where
+        F: Fn(&Self) -> bool + Send + Sync + 'static,
+    {
+        self.paint().when_value(predicate)
+    }
+
+    /// Set a not comparison with a predicate on the content, checked every time it is displayed
+    fn when_value_not<F>(self, predicate: F) -> Paint<Self>
+    where
+        F: Fn(&Self) -> bool + Send + Sync + 'static,
+    {
+        self.paint().when_value_not(predicate)
+    }
+
+    /// Start a conditional chain, applying the styles built by `styles` if `condition` is true
+    fn if_then<F>(self, condition: bool, styles: F) -> Paint<Self>
+    where
+        F: FnOnce(Style) -> Style,
+    {
+        self.paint().if_then(condition, styles)
+    }
+
+    /// Apply the Style of the first arm whose predicate matches `value`
+    fn select<V>(self, value: V, arms: &[SelectArm<'_, V>]) -> Paint<Self> {
+        self.paint().select(value, arms)
+    }
+
     /// Success style shortcut
     fn success(self) -> Paint<Self> {
         let mut paint = self.paint();
@@ -0,0 +1,257 @@
+use crate::{
+    env::PredefinedStyleType,
+    style::{Color, Decoration, Style, StyleColor, RGB},
+    styled::StyledString,
+};
+use core::fmt;
+
+/// Markup Error
+///
+/// Errors found while parsing markup. Positions are byte offsets into the markup text.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MarkupError {
+    /// A tag contains a word that is not a known colour, decoration or semantic style.
+    UnknownStyle { position: usize, name: String },
+    /// A tag is opened with `[` but never closed with `]`.
+    UnterminatedTag { position: usize },
+    /// A tag has no styles in it, for example `[]`.
+    EmptyTag { position: usize },
+    /// A closing tag was found when no tag is open.
+    UnexpectedClose { position: usize },
+    /// A named closing tag does not match the innermost open tag.
+    MismatchedClose {
+        position: usize,
+        expected: String,
+        found: String,
+    },
+    /// A tag is still open at the end of the markup.
+    UnclosedTag { position: usize, tag: String },
+}
+
+impl fmt::Display for MarkupError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MarkupError::UnknownStyle { position, name } => {
+                write!(f, "unknown style `{name}` at position {position}")
+            }
+            MarkupError::UnterminatedTag { position } => {
+                write!(f, "tag at position {position} is missing a closing `]`")
+            }
+            MarkupError::EmptyTag { position } => write!(f, "empty tag at position {position}"),
+            MarkupError::UnexpectedClose { position } => {
+                write!(f, "closing tag at position {position} has no open tag")
+            }
+            MarkupError::MismatchedClose {
+                position,
+                expected,
+                found,
+            } => write!(
+                f,
+                "closing tag `[/{found}]` at position {position} does not match open tag `[{expected}]`"
+            ),
+            MarkupError::UnclosedTag { position, tag } => {
+                write!(f, "tag `[{tag}]` at position {position} is never closed")
+            }
+        }
+    }
+}
+
+impl std::error::Error for MarkupError {}
+
+/// Parse inline markup into a `StyledString`.
+///
+/// Styles are opened with `[style ...]` and closed with `[/]`, or `[/style ...]` to check that
+/// the right tag is being closed. Tags nest, with inner tags adding to the outer styles.
+///
+/// A tag holds one or more of:
+/// - colour names such as `red` or `bright_blue`, `#hex`, `rgb(r,g,b)` and `color(n)` for the
+///   256 colour palette. Prefix a colour with `on` to set the background, for example `on white`.
+/// - decorations: `bold`, `dim`, `italic`, `underline` and `invert`.
+/// - semantic styles: `success`, `warning`, `error`, `info` and `debug`, which use the
+///   current predefined styles.
+///
+/// Write `\[` for a literal `[` and `\\` for a literal `\`.
+pub fn markup(text: &str) -> Result<StyledString, MarkupError> {
+    let mut styled = StyledString::new();
+    let mut stack: Vec<(usize, String, Style)> = Vec::new();
+    let mut current = Style::new();
+    let mut literal = String::new();
+    let mut chars = text.char_indices().peekable();
+
+    while let Some((position, c)) = chars.next() {
+        match c {
+            '\\' if matches!(chars.peek(), Some((_, '[' | '\\'))) => {
+                if let Some((_, escaped)) = chars.next() {
+                    literal.push(escaped);
+                }
+            }
+            '[' => {
+                let end = text[position..]
+                    .find(']')
+                    .map(|end| position + end)
+                    .ok_or(MarkupError::UnterminatedTag { position })?;
+                let tag = text[position + 1..end].trim();
+                while chars.peek().is_some_and(|(i, _)| *i <= end) {
+                    chars.next();
+                }
+
+                styled.push(current, std::mem::take(&mut literal));
+                if let Some(closing) = tag.strip_prefix('/') {
+                    let (_, open_tag, previous) = stack
+                        .pop()
+                        .ok_or(MarkupError::UnexpectedClose { position })?;
+                    let closing = normalize(closing);
+                    if !closing.is_empty() && closing != open_tag {
+                        return Err(MarkupError::MismatchedClose {
+                            position,
+                            expected: open_tag,
+                            found: closing,
+                        });
+                    }
+                    current = previous;
+                } else {
+                    let style = apply_tag(current, tag, position)?;
+                    stack.push((position, normalize(tag), current));
+                    current = style;
+                }
+            }
+            _ => literal.push(c),
+        }
+    }
+
+    if let Some((position, tag, _)) = stack.pop() {
+        return Err(MarkupError::UnclosedTag { position, tag });
+    }
+    styled.push(current, literal);
+
+    Ok(styled)
+}
+
+/// Escape text so that it is shown literally when inserted into markup.
+pub fn escape_markup(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('[', "\\[")
+}
+
+/// Put a tag in the form closing tags are compared in. Style names are case insensitive.
+fn normalize(tag: &str) -> String {
+    split_words(tag).join(" ").to_lowercase()
+}
+
+/// Split a tag into words on whitespace, keeping `rgb(1, 2, 3)` together.
+fn split_words(tag: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut depth = 0usize;
+    for c in tag.chars() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+        if c.is_whitespace() {
+            if depth == 0 && !word.is_empty() {
+                words.push(std::mem::take(&mut word));
+            }
+        } else {
+            word.push(c);
+        }
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+    words
+}
+
+fn apply_tag(mut style: Style, tag: &str, position: usize) -> Result<Style, MarkupError> {
+    let words = split_words(tag);
+    if words.is_empty() {
+        return Err(MarkupError::EmptyTag { position });
+    }
+
+    let unknown = |name: &str| MarkupError::UnknownStyle {
+        position,
+        name: name.to_string(),
+    };
+    let mut words = words.iter();
+    while let Some(word) = words.next() {
+        let word = word.to_ascii_lowercase();
+        if word == "on" {
+            let color = words.next().ok_or_else(|| unknown("on"))?;
+            style.bg = Some(parse_color(color).ok_or_else(|| unknown(color))?);
+        } else if let Some(decoration) = parse_decoration(&word) {
+            style = style.decoration(decoration);
+        } else if let Some(style_type) = parse_semantic(&word) {
+            style = style.patch(Style::predefined(style_type));
+        } else {
+            style.fg = Some(parse_color(&word).ok_or_else(|| unknown(&word))?);
+        }
+    }
+
+    Ok(style)
+}
+
+fn parse_decoration(word: &str) -> Option<Decoration> {
+    match word {
+        "bold" => Some(Decoration::Bold),
+        "dim" => Some(Decoration::Dim),
+        "italic" => Some(Decoration::Italic),
+        "underline" => Some(Decoration::Underline),
+        "invert" => Some(Decoration::Invert),
+        _ => None,
+    }
+}
+
+fn parse_semantic(word: &str) -> Option<PredefinedStyleType> {
+    match word {
+        "success" => Some(PredefinedStyleType::Success),
+        "warning" => Some(PredefinedStyleType::Warning),
+        "error" => Some(PredefinedStyleType::Error),
+        "info" => Some(PredefinedStyleType::Info),
+        "debug" => Some(PredefinedStyleType::Debug),
+        _ => None,
+    }
+}
+
+fn parse_color(word: &str) -> Option<StyleColor> {
+    let word = word.to_ascii_lowercase();
+    if word.starts_with('#') {
+        return RGB::new_from_hex(&word).map(StyleColor::Rgb);
+    }
+    if let Some(args) = word.strip_prefix("rgb(").and_then(|w| w.strip_suffix(')')) {
+        let channels: Vec<u8> = args
+            .split(',')
+            .map(|channel| channel.trim().parse().ok())
+            .collect::<Option<_>>()?;
+        return match channels[..] {
+            [r, g, b] => Some(StyleColor::Rgb(RGB::new(r, g, b))),
+            _ => None,
+        };
+    }
+    if let Some(index) = word
+        .strip_prefix("color(")
+        .and_then(|w| w.strip_suffix(')'))
+    {
+        return index.trim().parse().ok().map(StyleColor::Fixed);
+    }
+
+    let color = match word.replace('-', "_").as_str() {
+        "black" => Color::Black,
+        "red" => Color::Red,
+        "green" => Color::Green,
+        "yellow" => Color::Yellow,
+        "blue" => Color::Blue,
+        "magenta" => Color::Magenta,
+        "cyan" => Color::Cyan,
+        "white" => Color::White,
+        "bright_black" => Color::BrightBlack,
+        "bright_red" => Color::BrightRed,
+        "bright_green" => Color::BrightGreen,
+        "bright_yellow" => Color::BrightYellow,
+        "bright_blue" => Color::BrightBlue,
+        "bright_magenta" => Color::BrightMagenta,
+        "bright_cyan" => Color::BrightCyan,
+        "bright_white" => Color::BrightWhite,
+        _ => return None,
+    };
+    Some(StyleColor::Named(color))
+}
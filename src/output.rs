@@ -1,4 +1,4 @@
-use crate::{Color, Comparison, Decoration, RGB};
+use crate::{style::AdaptiveColor, Color, Comparison, Decoration, Style, RGB};
 
 /// Accepted output item types
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -7,8 +7,17 @@ pub enum OutputItem {
     BgColor(Color),
     FgRgb(RGB),
     BgRgb(RGB),
+    FgFixed(u8),
+    BgFixed(u8),
+    FgAdaptive(AdaptiveColor),
+    BgAdaptive(AdaptiveColor),
     Decoration(Decoration),
     Comparison(Comparison, Option<bool>),
+    /// A comparison whose expression is the result of a `Paint` predicate, by index, worked out
+    /// when the Paint is displayed.
+    Deferred(Comparison, usize),
+    /// A group of colours and decorations that is applied all together or not at all.
+    Style(Style),
 }
 
 /// Output
@@ -54,6 +63,26 @@ impl Output {
         self.items.push(OutputItem::BgRgb(rgb));
     }
 
+    /// Push a foreground 256 colour palette entry to output.
+    pub fn push_fg_fixed(&mut self, index: u8) {
+        self.items.push(OutputItem::FgFixed(index));
+    }
+
+    /// Push a background 256 colour palette entry to output.
+    pub fn push_bg_fixed(&mut self, index: u8) {
+        self.items.push(OutputItem::BgFixed(index));
+    }
+
+    /// Push a foreground adaptive colour to output.
+    pub fn push_fg_adaptive(&mut self, color: AdaptiveColor) {
+        self.items.push(OutputItem::FgAdaptive(color));
+    }
+
+    /// Push a background adaptive colour to output.
+    pub fn push_bg_adaptive(&mut self, color: AdaptiveColor) {
+        self.items.push(OutputItem::BgAdaptive(color));
+    }
+
     /// Push a decoration to output.
     pub fn push_decoration(&mut self, decoration: Decoration) {
         self.items.push(OutputItem::Decoration(decoration));
@@ -64,4 +93,14 @@ impl Output {
         self.items
             .push(OutputItem::Comparison(comparison, expression));
     }
+
+    /// Push a group of styles to output.
+    pub fn push_style(&mut self, style: Style) {
+        self.items.push(OutputItem::Style(style));
+    }
+
+    /// Push a comparison using the `Paint` predicate at `index` to output.
+    pub fn push_deferred(&mut self, comparison: Comparison, index: usize) {
+        self.items.push(OutputItem::Deferred(comparison, index));
+    }
 }
@@ -1,87 +1,275 @@
 use crate::{
-    env::{predefined_style, should_colorize, true_color_allowed, PredefinedStyleType},
+    env::{
+        color_level, predefined_style, should_colorize, true_color_override, ColorLevel,
+        PredefinedStyleType,
+    },
     output::{Output, OutputItem},
     stream,
-    style::{Color, Comparison, Decoration, RGB},
+    style::{
+        AdaptiveColor, Color, Comparison, Decoration, Palette, Style, StyleColor, ANSI_COLORS,
+        COLOR_BG_FIXED, COLOR_FIXED, RGB,
+    },
 };
 use core::fmt;
+#[cfg(unix)]
+use std::os::unix::io::AsRawFd;
+
+/// A condition evaluated against the content when a `Paint` is displayed.
+type Predicate<T> = Box<dyn Fn(&T) -> bool + Send + Sync>;
+
+/// An arm of `Paint::select`: a predicate on the selected value and the Style applied when it
+/// matches.
+pub type SelectArm<'a, V> = (&'a dyn Fn(&V) -> bool, Style);
 
 pub struct Paint<T> {
     pub(crate) inner: T,
-    pub(crate) enabled: bool,
     pub(crate) output: Output,
+    predicates: Vec<Predicate<T>>,
+    /// The stream the content is written to.
+    stream: stream::Stream,
+    /// The colour level detected for `stream` when an eager Paint was created.
+    detected_level: ColorLevel,
+    /// Whether the colour level of `stream` is detected every time the Paint is displayed.
+    lazy: bool,
+    /// The colour level used instead of the one detected for `stream`.
+    color_level: Option<ColorLevel>,
+    /// Whether a branch of the current `if_then` chain has been taken.
+    branch_taken: Option<bool>,
 }
 
 /// Paint
 ///
 /// Paint context for managing and displaying styles, including methods for chaining styles together.
 impl<T> Paint<T> {
-    /// Initialize a new Paint context for writing to std_out.
-    fn new(inner: T, enabled: bool) -> Self {
+    /// Initialize a new Paint context for writing to `stream`.
+    fn new(inner: T, stream: stream::Stream, lazy: bool) -> Self {
         Self {
             inner,
-            enabled,
             output: Output::new(),
+            predicates: Vec::new(),
+            stream,
+            detected_level: if lazy {
+                ColorLevel::None
+            } else {
+                color_level(stream)
+            },
+            lazy,
+            color_level: None,
+            branch_taken: None,
         }
     }
 
     /// Initialize a new Paint context for writing to std_out explicitly.
     pub fn new_stdout(inner: T) -> Self {
-        let enabled = should_colorize(stream::Stream::Stdout);
-        Self::new(inner, enabled)
+        Self::new(inner, stream::Stream::Stdout, false)
     }
 
     /// Initialize a new Paint context for writing to std_err explicitly.
     pub fn new_stderr(inner: T) -> Self {
-        let enabled = should_colorize(stream::Stream::Stderr);
-        Self::new(inner, enabled)
+        Self::new(inner, stream::Stream::Stderr, false)
+    }
+
+    /// Initialize a new Paint context for writing to any `Stream`.
+    pub fn new_for_stream(inner: T, stream: stream::Stream) -> Self {
+        Self::new(inner, stream, false)
+    }
+
+    /// Initialize a new Paint context for writing to a file, pipe or terminal, detecting
+    /// colour support from its file descriptor.
+    ///
+    /// ```rust,no_run
+    /// use rustyhues::Paint;
+    /// use std::fs::OpenOptions;
+    /// use std::io::Write;
+    ///
+    /// let mut tty = OpenOptions::new().write(true).open("/dev/tty").unwrap();
+    /// let prompt = Paint::for_writer("Password: ", &tty).bold();
+    /// write!(tty, "{prompt}").unwrap();
+    /// ```
+    #[cfg(unix)]
+    pub fn for_writer(inner: T, writer: &impl AsRawFd) -> Self {
+        Self::new_for_stream(inner, stream::Stream::Fd(writer.as_raw_fd()))
+    }
+
+    /// Initialize a new lazy Paint context for writing to std_out. Whether the content is
+    /// styled is worked out every time it is displayed, rather than when it is created, so
+    /// it follows later changes to `set_color_choice` and the other settings.
+    pub fn lazy_stdout(inner: T) -> Self {
+        Self::lazy_for_stream(inner, stream::Stream::Stdout)
+    }
+
+    /// Initialize a new lazy Paint context for writing to std_err. See `lazy_stdout`.
+    pub fn lazy_stderr(inner: T) -> Self {
+        Self::lazy_for_stream(inner, stream::Stream::Stderr)
+    }
+
+    /// Initialize a new lazy Paint context for writing to any `Stream`. See `lazy_stdout`.
+    pub fn lazy_for_stream(inner: T, stream: stream::Stream) -> Self {
+        Self::new(inner, stream, true)
+    }
+
+    /// Work out the Style applied to the content, evaluating conditions and predicates but
+    /// ignoring whether colours are enabled.
+    pub(crate) fn resolved_style(&self) -> Style {
+        let mut style = Style::new();
+        let mut should_paint = true;
+        for item in &self.output.items {
+            match item {
+                OutputItem::Comparison(comparison, expression) => {
+                    should_paint = compare(should_paint, *comparison, *expression);
+                }
+                OutputItem::Deferred(comparison, index) => {
+                    let expression = self.predicates[*index](&self.inner);
+                    should_paint = compare(should_paint, *comparison, Some(expression));
+                }
+                _ if !should_paint => {}
+                OutputItem::Style(group) => style = style.patch(*group),
+                OutputItem::FgColor(color) => style.fg = Some(StyleColor::Named(*color)),
+                OutputItem::BgColor(color) => style.bg = Some(StyleColor::Named(*color)),
+                OutputItem::FgRgb(rgb) => style.fg = Some(StyleColor::Rgb(*rgb)),
+                OutputItem::BgRgb(rgb) => style.bg = Some(StyleColor::Rgb(*rgb)),
+                OutputItem::FgFixed(index) => style.fg = Some(StyleColor::Fixed(*index)),
+                OutputItem::BgFixed(index) => style.bg = Some(StyleColor::Fixed(*index)),
+                OutputItem::FgAdaptive(color) => style.fg = Some(StyleColor::Adaptive(*color)),
+                OutputItem::BgAdaptive(color) => style.bg = Some(StyleColor::Adaptive(*color)),
+                OutputItem::Decoration(decoration) => style = style.decoration(*decoration),
+            }
+        }
+        style
+    }
+
+    /// Get the colour and decoration items that apply once comparisons and predicates are
+    /// evaluated, with style groups split into their items.
+    fn applied_items(&self) -> Vec<OutputItem> {
+        let mut items = Vec::new();
+        let mut should_paint = true;
+        for item in &self.output.items {
+            match item {
+                OutputItem::Comparison(comparison, expression) => {
+                    should_paint = compare(should_paint, *comparison, *expression);
+                }
+                OutputItem::Deferred(comparison, index) => {
+                    let expression = self.predicates[*index](&self.inner);
+                    should_paint = compare(should_paint, *comparison, Some(expression));
+                }
+                _ if !should_paint => {}
+                OutputItem::Style(style) => items.extend(style_items(*style)),
+                item => items.push(item.clone()),
+            }
+        }
+        items
+    }
+
+    /// Work out the colour level the content is displayed at. A level set with `color_level`
+    /// replaces the one detected for the stream, but only while the stream is colourized.
+    fn display_level(&self) -> ColorLevel {
+        match (self.lazy, self.color_level) {
+            (true, None) => color_level(self.stream),
+            (true, Some(level)) if should_colorize(self.stream) => level,
+            (false, Some(level)) if self.detected_level != ColorLevel::None => level,
+            (false, None) => self.detected_level,
+            _ => ColorLevel::None,
+        }
     }
 
     /// Set the foreground colour of the content.
     pub fn fg(mut self, color: Color) -> Self {
-        if self.enabled {
-            self.output.push_fg(color);
-        }
+        self.output.push_fg(color);
         self
     }
 
     /// Set the background colour of the content.
     pub fn bg(mut self, color: Color) -> Self {
-        if self.enabled {
-            self.output.push_bg(color);
-        }
+        self.output.push_bg(color);
         self
     }
 
     /// Set the decorations to be used on the content.
     pub fn decoration(mut self, decoration: Decoration) -> Self {
-        if self.enabled {
-            self.output.push_decoration(decoration);
-        }
+        self.output.push_decoration(decoration);
         self
     }
 
     /// Set an is or not expression
     pub fn set_is_not(mut self, comparison: Comparison, expression: Option<bool>) -> Self {
-        if self.enabled {
-            self.output.push_comparison(comparison, expression);
-        }
+        self.output.push_comparison(comparison, expression);
+        self
+    }
+
+    /// Set an is or not comparison whose expression is worked out when the content is
+    /// displayed.
+    pub fn set_deferred<F>(mut self, comparison: Comparison, predicate: F) -> Self
+    where
+        F: Fn(&T) -> bool + Send + Sync + 'static,
+    {
+        self.output.push_deferred(comparison, self.predicates.len());
+        self.predicates.push(Box::new(predicate));
         self
     }
 
     /// Set the foreground RGB value for the content.
     pub fn set_rgb(mut self, rgb_value: RGB) -> Self {
-        if self.enabled {
-            self.output.push_fg_rgb(rgb_value);
-        }
+        self.output.push_fg_rgb(rgb_value);
         self
     }
 
     /// Set the background RGB value for the content.
     pub fn set_bg_rgb(mut self, rgb_value: RGB) -> Self {
-        if self.enabled {
-            self.output.push_bg_rgb(rgb_value);
+        self.output.push_bg_rgb(rgb_value);
+        self
+    }
+
+    /// Set the foreground colour of the content to an entry of the 256 colour palette.
+    pub fn fixed(mut self, index: u8) -> Self {
+        self.output.push_fg_fixed(index);
+        self
+    }
+
+    /// Set the background colour of the content to an entry of the 256 colour palette.
+    pub fn bg_fixed(mut self, index: u8) -> Self {
+        self.output.push_bg_fixed(index);
+        self
+    }
+
+    /// Set the foreground colour of the content to an `AdaptiveColor`.
+    pub fn adaptive(mut self, color: AdaptiveColor) -> Self {
+        self.output.push_fg_adaptive(color);
+        self
+    }
+
+    /// Set the background colour of the content to an `AdaptiveColor`.
+    pub fn bg_adaptive(mut self, color: AdaptiveColor) -> Self {
+        self.output.push_bg_adaptive(color);
+        self
+    }
+
+    /// Apply all colours and decorations of a `Style` to the content.
+    pub fn style(self, style: Style) -> Self {
+        let mut new_self = match style.fg {
+            Some(StyleColor::Named(color)) => self.fg(color),
+            Some(StyleColor::Fixed(index)) => self.fixed(index),
+            Some(StyleColor::Rgb(rgb)) => self.set_rgb(rgb),
+            Some(StyleColor::Adaptive(color)) => self.adaptive(color),
+            None => self,
+        };
+        new_self = match style.bg {
+            Some(StyleColor::Named(color)) => new_self.bg(color),
+            Some(StyleColor::Fixed(index)) => new_self.bg_fixed(index),
+            Some(StyleColor::Rgb(rgb)) => new_self.set_bg_rgb(rgb),
+            Some(StyleColor::Adaptive(color)) => new_self.bg_adaptive(color),
+            None => new_self,
+        };
+        for decoration in style.decorations() {
+            new_self = new_self.decoration(decoration);
         }
+        new_self
+    }
+
+    /// Display colours at `level` instead of the one detected for the stream, downgrading those
+    /// it can't show. Streams that aren't colourized still display the content without styles,
+    /// as does `ColorLevel::None`.
+    pub fn color_level(mut self, level: ColorLevel) -> Self {
+        self.color_level = Some(level);
         self
     }
 
@@ -340,6 +528,120 @@ impl<T> Paint<T> {
         self.set_is_not(Comparison::Not, None)
     }
 
+    /// Set an is comparison with a condition checked every time the content is displayed
+    /// (For use when chaining styles).
+    pub fn when<F>(self, condition: F) -> Self
+    where
+        F: Fn() -> bool + Send + Sync + 'static,
+    {
+        self.set_deferred(Comparison::Is, move |_| condition())
+    }
+
+    /// Set a not comparison with a condition checked every time the content is displayed
+    /// (For use when chaining styles).
+    pub fn when_not<F>(self, condition: F) -> Self
+    where
+        F: Fn() -> bool + Send + Sync + 'static,
+    {
+        self.set_deferred(Comparison::Not, move |_| condition())
+    }
+
+    /// Set an is comparison with a predicate on the content, checked every time it is
+    /// displayed (For use when chaining styles).
+    pub fn when_value<F>(self, predicate: F) -> Self
+    where
+        F: Fn(&T) -> bool + Send + Sync + 'static,
+    {
+        self.set_deferred(Comparison::Is, predicate)
+    }
+
+    /// Set a not comparison with a predicate on the content, checked every time it is
+    /// displayed (For use when chaining styles).
+    pub fn when_value_not<F>(self, predicate: F) -> Self
+    where
+        F: Fn(&T) -> bool + Send + Sync + 'static,
+    {
+        self.set_deferred(Comparison::Not, predicate)
+    }
+
+    /// Apply a group of styles to the content, all together (For use when chaining styles).
+    pub fn style_group(mut self, style: Style) -> Self {
+        if !style.is_plain() {
+            self.output.push_style(style);
+        }
+        self
+    }
+
+    /// Start a conditional chain: apply the styles built by `styles` if `condition` is true
+    /// (For use when chaining styles).
+    ///
+    /// ```rust
+    /// use rustyhues::Stylize;
+    ///
+    /// let failed = 3;
+    /// let summary = format!("{failed} failed")
+    ///     .if_then(failed > 0, |s| s.red().bold())
+    ///     .else_then(|s| s.green());
+    /// ```
+    pub fn if_then<F>(mut self, condition: bool, styles: F) -> Self
+    where
+        F: FnOnce(Style) -> Style,
+    {
+        self.branch_taken = Some(condition);
+        if condition {
+            self = self.style_group(styles(Style::new()));
+        }
+        self
+    }
+
+    /// Continue a conditional chain: apply the styles built by `styles` if `condition` is true
+    /// and no earlier branch was taken (For use when chaining styles).
+    pub fn else_if_then<F>(self, condition: bool, styles: F) -> Self
+    where
+        F: FnOnce(Style) -> Style,
+    {
+        match self.branch_taken {
+            Some(false) => self.if_then(condition, styles),
+            _ => self,
+        }
+    }
+
+    /// End a conditional chain: apply the styles built by `styles` if no earlier branch was
+    /// taken (For use when chaining styles).
+    pub fn else_then<F>(mut self, styles: F) -> Self
+    where
+        F: FnOnce(Style) -> Style,
+    {
+        if self.branch_taken == Some(false) {
+            self = self.style_group(styles(Style::new()));
+        }
+        self.branch_taken = None;
+        self
+    }
+
+    /// Apply the Style of the first arm whose predicate matches `value`, like a `match`
+    /// (For use when chaining styles).
+    ///
+    /// ```rust
+    /// use rustyhues::{Style, Stylize};
+    ///
+    /// let load = 0.93;
+    /// let painted = format!("{load:.2}").select(
+    ///     load,
+    ///     &[
+    ///         (&|v: &f64| *v > 0.9, Style::new().red().bold()),
+    ///         (&|v: &f64| *v > 0.7, Style::new().yellow()),
+    ///         (&|_: &f64| true, Style::new().green()),
+    ///     ],
+    /// );
+    /// ```
+    pub fn select<V>(self, value: V, arms: &[SelectArm<'_, V>]) -> Self {
+        match arms.iter().find(|(predicate, _)| predicate(&value)) {
+            Some((_, style)) => self.style_group(*style),
+            None => self,
+        }
+    }
+
     /// Success style shortcut (For use when chaining styles).
     pub fn success(self) -> Self {
         let mut new_self = self;
@@ -423,68 +725,36 @@ impl<T> Paint<T> {
 
 impl<T: fmt::Display> fmt::Display for Paint<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        if !self.enabled {
+        let level = self.display_level();
+        if level == ColorLevel::None {
+            return self.inner.fmt(f);
+        }
+        let levels = Levels {
+            colors: level,
+            rgb: if self.color_level.is_none() && true_color_override() == Some(false) {
+                level.min(ColorLevel::Basic16)
+            } else {
+                level
+            },
+        };
+
+        // Items gated off by a comparison leave nothing to write, and an empty `\x1b[m` would
+        // reset any style around the content.
+        let items = self.applied_items();
+        if items.is_empty() {
             return self.inner.fmt(f);
         }
 
-        if !self.output.items.is_empty() {
-            f.write_str("\x1b[")?;
-            let mut first = true;
-            let mut should_paint = true;
-
-            for item in &self.output.items {
-                match item {
-                    OutputItem::FgColor(color) => {
-                        if should_paint {
-                            push_raw(f, &mut first, |f| f.write_str(color.fg_code()))?;
-                        }
-                    }
-                    OutputItem::BgColor(color) => {
-                        if should_paint {
-                            push_raw(f, &mut first, |f| f.write_str(color.bg_code()))?;
-                        }
-                    }
-                    OutputItem::FgRgb(rgb) => {
-                        if should_paint {
-                            if true_color_allowed() {
-                                push_raw(f, &mut first, |f| rgb.write_fg_sgr(f))?;
-                            } else if let Some(color) = rgb.closest_color() {
-                                push_raw(f, &mut first, |f| f.write_str(color.fg_code()))?;
-                            }
-                        }
-                    }
-                    OutputItem::BgRgb(rgb) => {
-                        if should_paint {
-                            if true_color_allowed() {
-                                push_raw(f, &mut first, |f| rgb.write_bg_sgr(f))?;
-                            } else if let Some(color) = rgb.closest_color() {
-                                push_raw(f, &mut first, |f| f.write_str(color.bg_code()))?;
-                            }
-                        }
-                    }
-                    OutputItem::Decoration(decoration) => {
-                        if should_paint {
-                            push_raw(f, &mut first, |f| f.write_str(decoration.code()))?;
-                        }
-                    }
-                    OutputItem::Comparison(comparison, expression) => {
-                        match (comparison, expression) {
-                            (Comparison::Is, Some(true)) => should_paint = true,
-                            (Comparison::Is, Some(false)) => should_paint = false,
-                            (Comparison::Not, Some(true)) => should_paint = false,
-                            (Comparison::Not, Some(false)) => should_paint = true,
-                            (_, None) => should_paint = !should_paint,
-                        }
-                    }
-                }
-            }
-
-            f.write_str("m")?;
+        f.write_str("\x1b[")?;
+        let mut first = true;
+        for item in &items {
+            write_item(f, &mut first, item, levels)?;
         }
+        f.write_str("m")?;
 
         self.inner.fmt(f)?;
 
-        if !self.output.items.is_empty() && self.output.should_reset {
+        if self.output.should_reset {
             f.write_str("\x1b[0m")?;
         }
 
@@ -492,6 +762,113 @@ impl<T: fmt::Display> fmt::Display for Paint<T> {
     }
 }
 
+/// The colour levels a Paint is displayed at. RGB colours can be limited further than the
+/// other colours by `env::set_true_color_allowed(Some(false))`.
+#[derive(Clone, Copy)]
+struct Levels {
+    colors: ColorLevel,
+    rgb: ColorLevel,
+}
+
+/// Write the SGR parameter of a colour or decoration item, downgrading colours that the
+/// colour level doesn't support.
+fn write_item(
+    f: &mut fmt::Formatter<'_>,
+    first: &mut bool,
+    item: &OutputItem,
+    levels: Levels,
+) -> fmt::Result {
+    match downgrade(item, levels) {
+        OutputItem::FgColor(color) => push_raw(f, first, |f| f.write_str(color.fg_code())),
+        OutputItem::BgColor(color) => push_raw(f, first, |f| f.write_str(color.bg_code())),
+        OutputItem::FgRgb(rgb) => push_raw(f, first, |f| rgb.write_fg_sgr(f)),
+        OutputItem::BgRgb(rgb) => push_raw(f, first, |f| rgb.write_bg_sgr(f)),
+        OutputItem::FgFixed(index) => push_raw(f, first, |f| write!(f, "{COLOR_FIXED}{index}")),
+        OutputItem::BgFixed(index) => push_raw(f, first, |f| write!(f, "{COLOR_BG_FIXED}{index}")),
+        OutputItem::Decoration(decoration) => {
+            push_raw(f, first, |f| f.write_str(decoration.code()))
+        }
+        // Adaptive colours are always replaced by `downgrade`.
+        OutputItem::FgAdaptive(_)
+        | OutputItem::BgAdaptive(_)
+        | OutputItem::Comparison(..)
+        | OutputItem::Deferred(..)
+        | OutputItem::Style(_) => Ok(()),
+    }
+}
+
+/// Replace a colour with the closest one available at the colour level.
+fn downgrade(item: &OutputItem, levels: Levels) -> OutputItem {
+    match *item {
+        OutputItem::FgRgb(rgb) => match levels.rgb {
+            ColorLevel::TrueColor => OutputItem::FgRgb(rgb),
+            ColorLevel::Ansi256 => OutputItem::FgFixed(rgb.closest_fixed()),
+            _ => OutputItem::FgColor(rgb_to_ansi(rgb)),
+        },
+        OutputItem::BgRgb(rgb) => match levels.rgb {
+            ColorLevel::TrueColor => OutputItem::BgRgb(rgb),
+            ColorLevel::Ansi256 => OutputItem::BgFixed(rgb.closest_fixed()),
+            _ => OutputItem::BgColor(rgb_to_ansi(rgb)),
+        },
+        OutputItem::FgAdaptive(color) => match levels.rgb {
+            ColorLevel::TrueColor => OutputItem::FgRgb(color.rgb),
+            ColorLevel::Ansi256 => OutputItem::FgFixed(color.fixed),
+            _ => OutputItem::FgColor(color.named),
+        },
+        OutputItem::BgAdaptive(color) => match levels.rgb {
+            ColorLevel::TrueColor => OutputItem::BgRgb(color.rgb),
+            ColorLevel::Ansi256 => OutputItem::BgFixed(color.fixed),
+            _ => OutputItem::BgColor(color.named),
+        },
+        OutputItem::FgFixed(index) if levels.colors < ColorLevel::Ansi256 => {
+            OutputItem::FgColor(fixed_to_ansi(index))
+        }
+        OutputItem::BgFixed(index) if levels.colors < ColorLevel::Ansi256 => {
+            OutputItem::BgColor(fixed_to_ansi(index))
+        }
+        ref item => item.clone(),
+    }
+}
+
+fn rgb_to_ansi(rgb: RGB) -> Color {
+    rgb.closest_color().unwrap_or(Color::White)
+}
+
+fn fixed_to_ansi(index: u8) -> Color {
+    match ANSI_COLORS.get(index as usize) {
+        Some(color) => *color,
+        None => rgb_to_ansi(Palette::VSCODE.fixed(index)),
+    }
+}
+
+/// Split a Style into the output items for its colours and decorations.
+fn style_items(style: Style) -> Vec<OutputItem> {
+    let mut items = Vec::new();
+    items.extend(style.fg.map(|color| match color {
+        StyleColor::Named(color) => OutputItem::FgColor(color),
+        StyleColor::Fixed(index) => OutputItem::FgFixed(index),
+        StyleColor::Rgb(rgb) => OutputItem::FgRgb(rgb),
+        StyleColor::Adaptive(color) => OutputItem::FgAdaptive(color),
+    }));
+    items.extend(style.bg.map(|color| match color {
+        StyleColor::Named(color) => OutputItem::BgColor(color),
+        StyleColor::Fixed(index) => OutputItem::BgFixed(index),
+        StyleColor::Rgb(rgb) => OutputItem::BgRgb(rgb),
+        StyleColor::Adaptive(color) => OutputItem::BgAdaptive(color),
+    }));
+    items.extend(style.decorations().map(OutputItem::Decoration));
+    items
+}
+
+/// Work out whether the following items are painted after a comparison.
+fn compare(should_paint: bool, comparison: Comparison, expression: Option<bool>) -> bool {
+    match (comparison, expression) {
+        (Comparison::Is, Some(expression)) => expression,
+        (Comparison::Not, Some(expression)) => !expression,
+        (_, None) => !should_paint,
+    }
+}
+
 fn push_raw<F>(f: &mut fmt::Formatter<'_>, first: &mut bool, mut write_param: F) -> fmt::Result
 where
     F: FnMut(&mut fmt::Formatter<'_>) -> fmt::Result,
@@ -0,0 +1,241 @@
+//! Draw a box around styled content, with an optional title and footer.
+//!
+//! ```rust
+//! use rustyhues::env::PredefinedStyleType;
+//! use rustyhues::panel::Panel;
+//!
+//! let panel = Panel::new("3 tests failed")
+//!     .title("Summary")
+//!     .semantic(PredefinedStyleType::Error);
+//! eprintln!("{panel}");
+//! ```
+use crate::{
+    border::BorderStyle,
+    env::PredefinedStyleType,
+    style::Style,
+    styled::StyledString,
+    truncate::{truncate, TruncateOptions},
+    wrap::{wrap, WrapOptions},
+};
+use core::fmt;
+
+/// Panel
+///
+/// A box drawn around styled content. Box drawing borders fall back to ASCII when the terminal
+/// can't display UTF-8 (see `env::unicode_supported()`), and displaying a Panel follows the same
+/// terminal detection rules as `Paint`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Panel {
+    content: StyledString,
+    title: Option<StyledString>,
+    footer: Option<StyledString>,
+    border: BorderStyle,
+    border_style: Style,
+    padding: usize,
+    vertical_padding: usize,
+    width: Option<usize>,
+}
+
+impl Panel {
+    /// Create a new Panel with rounded borders around some content.
+    pub fn new(content: impl Into<StyledString>) -> Self {
+        Self {
+            content: content.into(),
+            title: None,
+            footer: None,
+            border: BorderStyle::Rounded,
+            border_style: Style::new(),
+            padding: 1,
+            vertical_padding: 0,
+            width: None,
+        }
+    }
+
+    /// Set the title shown in the top border.
+    pub fn title(mut self, title: impl Into<StyledString>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Set the footer shown in the bottom border.
+    pub fn footer(mut self, footer: impl Into<StyledString>) -> Self {
+        self.footer = Some(footer.into());
+        self
+    }
+
+    /// Set the characters used for the border.
+    pub fn border(mut self, border: BorderStyle) -> Self {
+        self.border = border;
+        self
+    }
+
+    /// Set the style of the border. The title and footer use it too, unless they have their
+    /// own colours.
+    pub fn border_style(mut self, style: Style) -> Self {
+        self.border_style = style;
+        self
+    }
+
+    /// Style the border using one of the predefined styles, for example an error panel.
+    pub fn semantic(self, style_type: PredefinedStyleType) -> Self {
+        self.border_style(Style::predefined(style_type))
+    }
+
+    /// Set the number of spaces between the content and the left and right borders. Defaults to 1.
+    pub fn padding(mut self, padding: usize) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    /// Set the number of empty lines above and below the content. Defaults to 0.
+    pub fn vertical_padding(mut self, padding: usize) -> Self {
+        self.vertical_padding = padding;
+        self
+    }
+
+    /// Set the total width of the Panel, including borders. Content is wrapped to fit. By
+    /// default the Panel is as wide as its content.
+    pub fn width(mut self, width: usize) -> Self {
+        self.width = Some(width);
+        self
+    }
+
+    /// Render the Panel into styled lines joined with `\n`.
+    pub fn render(&self) -> StyledString {
+        let chars = self.border.fallback().chars();
+        let edge = if chars.is_some() { 1 } else { 0 };
+        let label_width =
+            |label: &Option<StyledString>| label.as_ref().map_or(0, |l| l.width() + 4);
+
+        let lines = match self.width {
+            Some(width) => {
+                let content_width = width.saturating_sub(2 * edge + 2 * self.padding).max(1);
+                wrap(&self.content, &WrapOptions::new(content_width))
+            }
+            None => self.content.lines(),
+        };
+        let inner_width = match self.width {
+            Some(width) => width.saturating_sub(2 * edge).max(1),
+            None => {
+                let content_width = lines.iter().map(StyledString::width).max().unwrap_or(0);
+                (content_width + 2 * self.padding)
+                    .max(label_width(&self.title))
+                    .max(label_width(&self.footer))
+            }
+        };
+        let content_width = inner_width.saturating_sub(2 * self.padding);
+
+        let mut rendered = Vec::new();
+        let horizontal = chars.map_or(' ', |chars| chars.horizontal);
+        let (top_left, top_right, bottom_left, bottom_right) =
+            chars.map_or((None, None, None, None), |chars| {
+                (
+                    Some(chars.top_left),
+                    Some(chars.top_right),
+                    Some(chars.bottom_left),
+                    Some(chars.bottom_right),
+                )
+            });
+
+        if chars.is_some() || self.title.is_some() {
+            rendered.push(self.rule(
+                top_left,
+                top_right,
+                horizontal,
+                inner_width,
+                self.title.as_ref(),
+                false,
+            ));
+        }
+
+        let vertical = chars.map(|chars| chars.vertical);
+        let blank = StyledString::new();
+        let padding_lines = std::iter::repeat_n(&blank, self.vertical_padding);
+        for line in padding_lines
+            .clone()
+            .chain(lines.iter())
+            .chain(padding_lines)
+        {
+            let line = truncate(line, &TruncateOptions::new(content_width));
+            let mut row = StyledString::new();
+            if let Some(vertical) = vertical {
+                row.push(self.border_style, vertical.to_string());
+            }
+            row.push_str(&" ".repeat(self.padding));
+            let fill = content_width - line.width();
+            row.append(line);
+            row.push_str(&" ".repeat(fill + self.padding));
+            if let Some(vertical) = vertical {
+                row.push(self.border_style, vertical.to_string());
+            }
+            rendered.push(row);
+        }
+
+        if chars.is_some() || self.footer.is_some() {
+            rendered.push(self.rule(
+                bottom_left,
+                bottom_right,
+                horizontal,
+                inner_width,
+                self.footer.as_ref(),
+                true,
+            ));
+        }
+
+        let mut panel = StyledString::new();
+        for (i, line) in rendered.into_iter().enumerate() {
+            if i > 0 {
+                panel.push_str("\n");
+            }
+            panel.append(line);
+        }
+        panel
+    }
+
+    /// Draw a top or bottom border, with a label placed on the left (title) or right (footer).
+    fn rule(
+        &self,
+        left: Option<char>,
+        right: Option<char>,
+        horizontal: char,
+        inner_width: usize,
+        label: Option<&StyledString>,
+        align_right: bool,
+    ) -> StyledString {
+        let mut rule = StyledString::new();
+        if let Some(left) = left {
+            rule.push(self.border_style, left.to_string());
+        }
+        match label {
+            // A label needs room for a horizontal and a space on either side.
+            Some(label) if inner_width >= 4 => {
+                let label = truncate(label, &TruncateOptions::new(inner_width.saturating_sub(4)));
+                let fill = inner_width.saturating_sub(label.width() + 4);
+                let (before, after) = if align_right {
+                    (fill + 1, 1)
+                } else {
+                    (1, fill + 1)
+                };
+                rule.push(self.border_style, horizontal.to_string().repeat(before));
+                rule.push_str(" ");
+                rule.append(label.with_base_style(self.border_style));
+                rule.push_str(" ");
+                rule.push(self.border_style, horizontal.to_string().repeat(after));
+            }
+            _ => rule.push(
+                self.border_style,
+                horizontal.to_string().repeat(inner_width),
+            ),
+        }
+        if let Some(right) = right {
+            rule.push(self.border_style, right.to_string());
+        }
+        rule
+    }
+}
+
+impl fmt::Display for Panel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.render().fmt(f)
+    }
+}
@@ -0,0 +1,498 @@
+//! Pretty-print JSON text with syntax colours, and `key: value` listings with aligned keys.
+//!
+//! ```rust
+//! use rustyhues::pretty::{Json, KeyValue};
+//!
+//! let json = Json::new().render(r#"{"name":"rustyhues","tags":["color","terminal"]}"#).unwrap();
+//! println!("{json}");
+//!
+//! let listing = KeyValue::new()
+//!     .entry("Name", "rustyhues")
+//!     .entry("License", "MIT");
+//! println!("{listing}");
+//! ```
+use crate::{
+    ansi::plain_width,
+    style::{Color, Decoration, Style},
+    styled::StyledString,
+    table::Align,
+};
+use core::fmt;
+
+/// JSON Error
+///
+/// The reasons JSON text can't be pretty-printed. Positions are byte offsets into the text.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum JsonError {
+    /// The text ended in the middle of a value.
+    UnexpectedEnd,
+    /// A character that can't appear here, such as a missing comma.
+    UnexpectedCharacter { position: usize, found: char },
+    /// A number that doesn't follow the JSON grammar, such as `01` or `1.`.
+    InvalidNumber { position: usize },
+    /// A `\` escape in a string that JSON doesn't allow.
+    InvalidEscape { position: usize },
+    /// A control character inside a string.
+    ControlCharacter { position: usize },
+    /// More text after the top level value.
+    TrailingCharacters { position: usize },
+    /// Objects and arrays nested more than `MAX_DEPTH` levels deep.
+    TooDeep { position: usize },
+}
+
+/// The deepest nesting of objects and arrays `Json::render` accepts.
+pub const MAX_DEPTH: usize = 128;
+
+impl fmt::Display for JsonError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JsonError::UnexpectedEnd => write!(f, "unexpected end of JSON"),
+            JsonError::UnexpectedCharacter { position, found } => {
+                write!(f, "unexpected character `{found}` at position {position}")
+            }
+            JsonError::InvalidNumber { position } => {
+                write!(f, "invalid number at position {position}")
+            }
+            JsonError::InvalidEscape { position } => {
+                write!(f, "invalid escape at position {position}")
+            }
+            JsonError::ControlCharacter { position } => {
+                write!(f, "control character in string at position {position}")
+            }
+            JsonError::TrailingCharacters { position } => {
+                write!(f, "trailing characters at position {position}")
+            }
+            JsonError::TooDeep { position } => {
+                write!(
+                    f,
+                    "nesting deeper than {MAX_DEPTH} levels at position {position}"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for JsonError {}
+
+/// JSON
+///
+/// Settings for pretty-printing JSON text: the style of each kind of token, the indentation
+/// and whether the output is compact. Strings and numbers are kept exactly as written.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Json {
+    key_style: Style,
+    string_style: Style,
+    number_style: Style,
+    bool_style: Style,
+    null_style: Style,
+    punctuation_style: Style,
+    indent: usize,
+    compact: bool,
+}
+
+impl Json {
+    /// Create new Json settings with colours similar to `jq` and an indentation of 2 spaces.
+    pub fn new() -> Self {
+        Self {
+            key_style: Style::new().fg(Color::Blue).decoration(Decoration::Bold),
+            string_style: Style::new().fg(Color::Green),
+            number_style: Style::new().fg(Color::Cyan),
+            bool_style: Style::new().fg(Color::Yellow),
+            null_style: Style::new().fg(Color::BrightBlack),
+            punctuation_style: Style::new(),
+            indent: 2,
+            compact: false,
+        }
+    }
+
+    /// Set the style of object keys.
+    pub fn key_style(mut self, style: Style) -> Self {
+        self.key_style = style;
+        self
+    }
+
+    /// Set the style of string values.
+    pub fn string_style(mut self, style: Style) -> Self {
+        self.string_style = style;
+        self
+    }
+
+    /// Set the style of numbers.
+    pub fn number_style(mut self, style: Style) -> Self {
+        self.number_style = style;
+        self
+    }
+
+    /// Set the style of `true` and `false`.
+    pub fn bool_style(mut self, style: Style) -> Self {
+        self.bool_style = style;
+        self
+    }
+
+    /// Set the style of `null`.
+    pub fn null_style(mut self, style: Style) -> Self {
+        self.null_style = style;
+        self
+    }
+
+    /// Set the style of brackets, braces, colons and commas.
+    pub fn punctuation_style(mut self, style: Style) -> Self {
+        self.punctuation_style = style;
+        self
+    }
+
+    /// Set the number of spaces per level of nesting.
+    pub fn indent(mut self, indent: usize) -> Self {
+        self.indent = indent;
+        self
+    }
+
+    /// Print everything on a single line without spaces.
+    pub fn compact(mut self, compact: bool) -> Self {
+        self.compact = compact;
+        self
+    }
+
+    /// Check and pretty-print JSON text.
+    pub fn render(&self, text: &str) -> Result<StyledString, JsonError> {
+        let mut printer = Printer {
+            json: self,
+            text,
+            position: 0,
+            output: StyledString::new(),
+        };
+        printer.skip_whitespace();
+        printer.value(0)?;
+        printer.skip_whitespace();
+        if printer.position < text.len() {
+            return Err(JsonError::TrailingCharacters {
+                position: printer.position,
+            });
+        }
+        Ok(printer.output)
+    }
+}
+
+impl Default for Json {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Pretty-print JSON text using the default `Json` settings.
+pub fn pretty_json(text: &str) -> Result<StyledString, JsonError> {
+    Json::new().render(text)
+}
+
+struct Printer<'a> {
+    json: &'a Json,
+    text: &'a str,
+    position: usize,
+    output: StyledString,
+}
+
+impl<'a> Printer<'a> {
+    fn peek(&self) -> Option<char> {
+        self.text[self.position..].chars().next()
+    }
+
+    fn unexpected(&self) -> JsonError {
+        match self.peek() {
+            Some(found) => JsonError::UnexpectedCharacter {
+                position: self.position,
+                found,
+            },
+            None => JsonError::UnexpectedEnd,
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = &self.text[self.position..];
+        let trimmed = rest.trim_start_matches([' ', '\t', '\n', '\r']);
+        self.position += rest.len() - trimmed.len();
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), JsonError> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some(c) if c == expected => {
+                self.position += 1;
+                Ok(())
+            }
+            _ => Err(self.unexpected()),
+        }
+    }
+
+    fn punctuation(&mut self, text: &str) {
+        self.output.push(self.json.punctuation_style, text);
+    }
+
+    fn newline(&mut self, depth: usize) {
+        if !self.json.compact {
+            self.output.push_str("\n");
+            self.output.push_str(&" ".repeat(depth * self.json.indent));
+        }
+    }
+
+    fn value(&mut self, depth: usize) -> Result<(), JsonError> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('{') => self.container(depth, '}', true),
+            Some('[') => self.container(depth, ']', false),
+            Some('"') => {
+                let string = self.string()?;
+                self.output.push(self.json.string_style, string);
+                Ok(())
+            }
+            Some('-' | '0'..='9') => {
+                let number = self.number()?;
+                self.output.push(self.json.number_style, number);
+                Ok(())
+            }
+            Some(_) => {
+                for (word, style) in [
+                    ("true", self.json.bool_style),
+                    ("false", self.json.bool_style),
+                    ("null", self.json.null_style),
+                ] {
+                    if self.text[self.position..].starts_with(word) {
+                        self.position += word.len();
+                        self.output.push(style, word);
+                        return Ok(());
+                    }
+                }
+                Err(self.unexpected())
+            }
+            None => Err(JsonError::UnexpectedEnd),
+        }
+    }
+
+    /// Print an object or array, starting at its opening bracket.
+    fn container(&mut self, depth: usize, close: char, object: bool) -> Result<(), JsonError> {
+        if depth >= MAX_DEPTH {
+            return Err(JsonError::TooDeep {
+                position: self.position,
+            });
+        }
+        let open = &self.text[self.position..self.position + 1];
+        self.position += 1;
+        self.punctuation(open);
+        self.skip_whitespace();
+        if self.peek() == Some(close) {
+            self.position += 1;
+            self.punctuation(&close.to_string());
+            return Ok(());
+        }
+
+        loop {
+            self.newline(depth + 1);
+            if object {
+                self.skip_whitespace();
+                if self.peek() != Some('"') {
+                    return Err(self.unexpected());
+                }
+                let key = self.string()?;
+                self.output.push(self.json.key_style, key);
+                self.expect(':')?;
+                self.punctuation(":");
+                if !self.json.compact {
+                    self.output.push_str(" ");
+                }
+            }
+            self.value(depth + 1)?;
+            self.skip_whitespace();
+            match self.peek() {
+                Some(',') => {
+                    self.position += 1;
+                    self.punctuation(",");
+                }
+                Some(c) if c == close => {
+                    self.position += 1;
+                    self.newline(depth);
+                    self.punctuation(&close.to_string());
+                    return Ok(());
+                }
+                _ => return Err(self.unexpected()),
+            }
+        }
+    }
+
+    /// Check a string, starting at its opening quote, and return it with its quotes.
+    fn string(&mut self) -> Result<&'a str, JsonError> {
+        let start = self.position;
+        let mut chars = self.text[start + 1..].char_indices();
+        while let Some((offset, c)) = chars.next() {
+            let position = start + 1 + offset;
+            match c {
+                '"' => {
+                    self.position = position + 1;
+                    return Ok(&self.text[start..self.position]);
+                }
+                '\\' => match chars.next() {
+                    Some((_, '"' | '\\' | '/' | 'b' | 'f' | 'n' | 'r' | 't')) => {}
+                    Some((_, 'u')) => {
+                        for _ in 0..4 {
+                            match chars.next() {
+                                Some((_, digit)) if digit.is_ascii_hexdigit() => {}
+                                Some(_) => return Err(JsonError::InvalidEscape { position }),
+                                None => return Err(JsonError::UnexpectedEnd),
+                            }
+                        }
+                    }
+                    Some(_) => return Err(JsonError::InvalidEscape { position }),
+                    None => return Err(JsonError::UnexpectedEnd),
+                },
+                c if c < ' ' => return Err(JsonError::ControlCharacter { position }),
+                _ => {}
+            }
+        }
+        Err(JsonError::UnexpectedEnd)
+    }
+
+    /// Check a number and return it as written.
+    fn number(&mut self) -> Result<&'a str, JsonError> {
+        let start = self.position;
+        let bytes = self.text.as_bytes();
+        let digits = |from: usize| {
+            bytes[from..]
+                .iter()
+                .take_while(|b| b.is_ascii_digit())
+                .count()
+        };
+        let invalid = JsonError::InvalidNumber { position: start };
+
+        let mut end = start;
+        if bytes[end] == b'-' {
+            end += 1;
+        }
+        match digits(end) {
+            0 => return Err(invalid),
+            count if count > 1 && bytes[end] == b'0' => return Err(invalid),
+            count => end += count,
+        }
+        if bytes.get(end) == Some(&b'.') {
+            match digits(end + 1) {
+                0 => return Err(invalid),
+                count => end += 1 + count,
+            }
+        }
+        if matches!(bytes.get(end), Some(b'e' | b'E')) {
+            end += 1;
+            if matches!(bytes.get(end), Some(b'+' | b'-')) {
+                end += 1;
+            }
+            match digits(end) {
+                0 => return Err(invalid),
+                count => end += count,
+            }
+        }
+        self.position = end;
+        Ok(&self.text[start..end])
+    }
+}
+
+/// Key Value
+///
+/// A listing of `key: value` lines with the values lined up. Values spanning several lines are
+/// indented to the value column. Displaying a KeyValue follows the same terminal detection
+/// rules as `Paint`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KeyValue {
+    entries: Vec<(StyledString, StyledString)>,
+    key_style: Style,
+    separator: String,
+    align: Align,
+}
+
+impl KeyValue {
+    /// Create a new, empty KeyValue listing with bold keys.
+    pub fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+            key_style: Style::new().decoration(Decoration::Bold),
+            separator: String::from(":"),
+            align: Align::Left,
+        }
+    }
+
+    /// Add an entry.
+    pub fn entry(mut self, key: impl Into<StyledString>, value: impl Into<StyledString>) -> Self {
+        self.push(key, value);
+        self
+    }
+
+    /// Add an entry to an existing KeyValue listing.
+    pub fn push(&mut self, key: impl Into<StyledString>, value: impl Into<StyledString>) {
+        self.entries.push((key.into(), value.into()));
+    }
+
+    /// Set the style of keys. Key styles take priority over it.
+    pub fn key_style(mut self, style: Style) -> Self {
+        self.key_style = style;
+        self
+    }
+
+    /// Set the text after each key. Defaults to `:`.
+    pub fn separator(mut self, separator: &str) -> Self {
+        self.separator = separator.to_string();
+        self
+    }
+
+    /// Set the alignment of keys within the key column.
+    pub fn align_keys(mut self, align: Align) -> Self {
+        self.align = align;
+        self
+    }
+
+    /// Render the listing into styled lines joined with `\n`.
+    pub fn render(&self) -> StyledString {
+        let key_width = self
+            .entries
+            .iter()
+            .map(|(key, _)| key.width() + plain_width(&self.separator))
+            .max()
+            .unwrap_or(0);
+
+        let mut rendered = StyledString::new();
+        for (i, (key, value)) in self.entries.iter().enumerate() {
+            if i > 0 {
+                rendered.push_str("\n");
+            }
+            let mut label = key.with_base_style(self.key_style);
+            label.push_str(&self.separator);
+            let extra = key_width - label.width();
+            let (left, right) = match self.align {
+                Align::Left => (0, extra),
+                Align::Right => (extra, 0),
+                Align::Center => (extra / 2, extra - extra / 2),
+            };
+            rendered.push_str(&" ".repeat(left));
+            rendered.append(label);
+            rendered.push_str(&" ".repeat(right));
+
+            for (j, line) in value.lines().into_iter().enumerate() {
+                if j > 0 {
+                    rendered.push_str("\n");
+                    rendered.push_str(&" ".repeat(key_width));
+                }
+                if !line.is_empty() {
+                    rendered.push_str(" ");
+                    rendered.append(line);
+                }
+            }
+        }
+        rendered
+    }
+}
+
+impl Default for KeyValue {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for KeyValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.render().fmt(f)
+    }
+}
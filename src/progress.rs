@@ -0,0 +1,505 @@
+//! Progress bars and spinners redrawn on stderr.
+//!
+//! When stderr is interactive (see `env::should_colorize()`), the current line is redrawn in
+//! place using the predefined styles: info while running, success when finished and error when
+//! failed. Otherwise a plain line is printed every few seconds, which keeps CI logs readable.
+//!
+//! ```rust
+//! use rustyhues::progress::ProgressBar;
+//!
+//! let mut bar = ProgressBar::new(3).template("{bar} {pos}/{len} {msg}");
+//! for file in ["a.txt", "b.txt", "c.txt"] {
+//!     bar.set_message(file);
+//!     bar.inc(1);
+//! }
+//! bar.finish_with_message("copied");
+//! ```
+use crate::{
+    env::{detect_ci, should_colorize, unicode_supported, PredefinedStyleType},
+    stream::Stream,
+    style::{Decoration, Style},
+    styled::StyledString,
+};
+use std::{
+    io::{self, Write},
+    time::{Duration, Instant},
+};
+
+/// Clock
+///
+/// A source of time for progress output, so tests can control elapsed time, ETA and throughput.
+pub trait Clock: Send {
+    /// Get the time passed since a fixed starting point.
+    fn now(&self) -> Duration;
+}
+
+/// System Clock
+///
+/// A `Clock` measuring real time since it was created.
+#[derive(Clone, Copy, Debug)]
+pub struct SystemClock {
+    start: Instant,
+}
+
+impl SystemClock {
+    /// Create a new SystemClock starting now.
+    pub fn new() -> Self {
+        Self {
+            start: Instant::now(),
+        }
+    }
+}
+
+impl Default for SystemClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for SystemClock {
+    fn now(&self) -> Duration {
+        self.start.elapsed()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum State {
+    Running,
+    Finished,
+    Failed,
+}
+
+impl State {
+    fn style(self) -> Style {
+        Style::predefined(match self {
+            State::Running => PredefinedStyleType::Info,
+            State::Finished => PredefinedStyleType::Success,
+            State::Failed => PredefinedStyleType::Error,
+        })
+    }
+}
+
+/// Writes lines for a progress bar or spinner, deciding how and when to redraw.
+struct Drawer {
+    writer: Box<dyn Write + Send>,
+    clock: Box<dyn Clock>,
+    interactive: bool,
+    started: Duration,
+    last_draw: Option<Duration>,
+    draw_interval: Duration,
+    plain_interval: Duration,
+}
+
+impl Drawer {
+    fn new() -> Self {
+        let clock = SystemClock::new();
+        Self {
+            writer: Box::new(io::stderr()),
+            started: clock.now(),
+            clock: Box::new(clock),
+            // CI logs show colours but can't redraw a line in place.
+            interactive: should_colorize(Stream::Stderr) && detect_ci().is_none(),
+            last_draw: None,
+            draw_interval: Duration::from_millis(50),
+            plain_interval: Duration::from_secs(5),
+        }
+    }
+
+    fn set_clock(&mut self, clock: Box<dyn Clock>) {
+        self.started = clock.now();
+        self.last_draw = None;
+        self.clock = clock;
+    }
+
+    fn elapsed(&self) -> Duration {
+        self.clock.now().saturating_sub(self.started)
+    }
+
+    /// Draw a line, unless the last one was drawn too recently. Final lines are always drawn.
+    fn draw(&mut self, line: &StyledString, last: bool) {
+        let now = self.clock.now();
+        let interval = if self.interactive {
+            self.draw_interval
+        } else {
+            self.plain_interval
+        };
+        if let (false, Some(previous)) = (last, self.last_draw) {
+            if now.saturating_sub(previous) < interval {
+                return;
+            }
+        }
+        self.last_draw = Some(now);
+
+        // Progress output is best effort, so write errors are ignored.
+        let _ = if self.interactive {
+            let end = if last { "\n" } else { "" };
+            write!(self.writer, "\r\x1b[2K{}{end}", line.to_ansi())
+        } else {
+            writeln!(self.writer, "{}", line.plain_text())
+        };
+        let _ = self.writer.flush();
+    }
+}
+
+/// Progress Bar
+///
+/// A bar showing how far through a known amount of work a task is. The template can use
+/// `{bar}`, `{pos}`, `{len}`, `{percent}`, `{elapsed}`, `{eta}`, `{per_sec}`, `{spinner}` and
+/// `{msg}`.
+pub struct ProgressBar {
+    len: u64,
+    pos: u64,
+    message: String,
+    template: String,
+    bar_width: usize,
+    ticks: usize,
+    state: State,
+    drawer: Drawer,
+}
+
+impl ProgressBar {
+    /// Create a new ProgressBar for `len` steps, drawn on stderr.
+    pub fn new(len: u64) -> Self {
+        Self {
+            len,
+            pos: 0,
+            message: String::new(),
+            template: String::from("{bar} {pos}/{len} {percent} ETA {eta} {msg}"),
+            bar_width: 30,
+            ticks: 0,
+            state: State::Running,
+            drawer: Drawer::new(),
+        }
+    }
+
+    /// Set the template used to draw the ProgressBar.
+    pub fn template(mut self, template: &str) -> Self {
+        self.template = template.to_string();
+        self
+    }
+
+    /// Set the width of `{bar}` in columns. Defaults to 30.
+    pub fn bar_width(mut self, width: usize) -> Self {
+        self.bar_width = width;
+        self
+    }
+
+    /// Write somewhere other than stderr.
+    pub fn writer(mut self, writer: impl Write + Send + 'static) -> Self {
+        self.drawer.writer = Box::new(writer);
+        self
+    }
+
+    /// Use a different `Clock`, for example a fake one in tests.
+    pub fn clock(mut self, clock: impl Clock + 'static) -> Self {
+        self.drawer.set_clock(Box::new(clock));
+        self
+    }
+
+    /// Override whether the line is redrawn in place with colours (`true`) or printed as plain
+    /// lines (`false`). By default this follows `env::should_colorize(Stream::Stderr)`, except
+    /// in CI systems.
+    pub fn interactive(mut self, interactive: bool) -> Self {
+        self.drawer.interactive = interactive;
+        self
+    }
+
+    /// Set how often plain lines are printed when not interactive. Defaults to 5 seconds.
+    pub fn plain_interval(mut self, interval: Duration) -> Self {
+        self.drawer.plain_interval = interval;
+        self
+    }
+
+    /// Get the current position.
+    pub fn position(&self) -> u64 {
+        self.pos
+    }
+
+    /// Set the current position and redraw.
+    pub fn set_position(&mut self, pos: u64) {
+        self.pos = pos.min(self.len);
+        self.draw(false);
+    }
+
+    /// Advance the position and redraw.
+    pub fn inc(&mut self, delta: u64) {
+        self.set_position(self.pos.saturating_add(delta));
+    }
+
+    /// Move `{spinner}` to the next frame and redraw, for example while waiting on a slow
+    /// step. Every redraw also moves it on.
+    pub fn tick(&mut self) {
+        self.draw(false);
+    }
+
+    /// Change the number of steps.
+    pub fn set_length(&mut self, len: u64) {
+        self.len = len;
+        self.set_position(self.pos);
+    }
+
+    /// Set the message shown by `{msg}` and redraw.
+    pub fn set_message(&mut self, message: impl Into<String>) {
+        self.message = message.into();
+        self.draw(false);
+    }
+
+    /// Fill the bar and draw it for the last time in the success style.
+    pub fn finish(&mut self) {
+        self.pos = self.len;
+        self.state = State::Finished;
+        self.draw(true);
+    }
+
+    /// Fill the bar and draw it for the last time with a new message.
+    pub fn finish_with_message(&mut self, message: impl Into<String>) {
+        self.message = message.into();
+        self.finish();
+    }
+
+    /// Draw the bar for the last time in the error style, keeping its position.
+    pub fn fail(&mut self, message: impl Into<String>) {
+        self.message = message.into();
+        self.state = State::Failed;
+        self.draw(true);
+    }
+
+    /// Render the current line without drawing it.
+    pub fn render(&self) -> StyledString {
+        let elapsed = self.drawer.elapsed();
+        render_template(&self.template, |field| match field {
+            "bar" => Some(self.bar()),
+            "pos" => Some(self.pos.to_string().into()),
+            "len" => Some(self.len.to_string().into()),
+            "percent" => Some(format!("{}%", percent(self.pos, self.len)).into()),
+            "elapsed" => Some(format_duration(elapsed).into()),
+            "eta" => Some(eta(self.pos, self.len, elapsed).into()),
+            "per_sec" => Some(per_sec(self.pos, elapsed).into()),
+            "spinner" => Some(spinner_frame(self.state, self.ticks)),
+            "msg" => Some(self.message.as_str().into()),
+            _ => None,
+        })
+    }
+
+    fn bar(&self) -> StyledString {
+        let filled = if self.len == 0 {
+            0
+        } else {
+            (self.bar_width as u128 * self.pos as u128 / self.len as u128) as usize
+        };
+        let (full, empty) = if unicode_supported() {
+            ('█', '░')
+        } else {
+            ('#', '-')
+        };
+        let mut bar = StyledString::styled(self.state.style(), full.to_string().repeat(filled));
+        bar.push(
+            Style::new().decoration(Decoration::Dim),
+            empty.to_string().repeat(self.bar_width - filled),
+        );
+        bar
+    }
+
+    fn draw(&mut self, last: bool) {
+        let line = self.render();
+        self.drawer.draw(&line, last);
+        self.ticks = self.ticks.wrapping_add(1);
+    }
+}
+
+/// Spinner
+///
+/// An animation for work of unknown length. The template can use `{spinner}`, `{elapsed}`
+/// and `{msg}`.
+pub struct Spinner {
+    message: String,
+    template: String,
+    ticks: usize,
+    state: State,
+    drawer: Drawer,
+}
+
+impl Spinner {
+    /// Create a new Spinner, drawn on stderr.
+    pub fn new() -> Self {
+        Self {
+            message: String::new(),
+            template: String::from("{spinner} {msg}"),
+            ticks: 0,
+            state: State::Running,
+            drawer: Drawer::new(),
+        }
+    }
+
+    /// Set the template used to draw the Spinner.
+    pub fn template(mut self, template: &str) -> Self {
+        self.template = template.to_string();
+        self
+    }
+
+    /// Write somewhere other than stderr.
+    pub fn writer(mut self, writer: impl Write + Send + 'static) -> Self {
+        self.drawer.writer = Box::new(writer);
+        self
+    }
+
+    /// Use a different `Clock`, for example a fake one in tests.
+    pub fn clock(mut self, clock: impl Clock + 'static) -> Self {
+        self.drawer.set_clock(Box::new(clock));
+        self
+    }
+
+    /// Override whether the line is redrawn in place with colours (`true`) or printed as plain
+    /// lines (`false`). By default this follows `env::should_colorize(Stream::Stderr)`, except
+    /// in CI systems.
+    pub fn interactive(mut self, interactive: bool) -> Self {
+        self.drawer.interactive = interactive;
+        self
+    }
+
+    /// Set how often plain lines are printed when not interactive. Defaults to 5 seconds.
+    pub fn plain_interval(mut self, interval: Duration) -> Self {
+        self.drawer.plain_interval = interval;
+        self
+    }
+
+    /// Move to the next frame and redraw.
+    pub fn tick(&mut self) {
+        self.ticks = self.ticks.wrapping_add(1);
+        self.draw(false);
+    }
+
+    /// Set the message shown by `{msg}` and redraw.
+    pub fn set_message(&mut self, message: impl Into<String>) {
+        self.message = message.into();
+        self.draw(false);
+    }
+
+    /// Draw the Spinner for the last time in the success style.
+    pub fn finish(&mut self) {
+        self.state = State::Finished;
+        self.draw(true);
+    }
+
+    /// Draw the Spinner for the last time with a new message.
+    pub fn finish_with_message(&mut self, message: impl Into<String>) {
+        self.message = message.into();
+        self.finish();
+    }
+
+    /// Draw the Spinner for the last time in the error style.
+    pub fn fail(&mut self, message: impl Into<String>) {
+        self.message = message.into();
+        self.state = State::Failed;
+        self.draw(true);
+    }
+
+    /// Render the current line without drawing it.
+    pub fn render(&self) -> StyledString {
+        let elapsed = self.drawer.elapsed();
+        render_template(&self.template, |field| match field {
+            "spinner" => Some(spinner_frame(self.state, self.ticks)),
+            "elapsed" => Some(format_duration(elapsed).into()),
+            "msg" => Some(self.message.as_str().into()),
+            _ => None,
+        })
+    }
+
+    fn draw(&mut self, last: bool) {
+        let line = self.render();
+        self.drawer.draw(&line, last);
+    }
+}
+
+impl Default for Spinner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+const FRAMES: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+const ASCII_FRAMES: [&str; 4] = ["|", "/", "-", "\\"];
+
+fn spinner_frame(state: State, ticks: usize) -> StyledString {
+    let unicode = unicode_supported();
+    let frame = match (state, unicode) {
+        (State::Running, true) => FRAMES[ticks % FRAMES.len()],
+        (State::Running, false) => ASCII_FRAMES[ticks % ASCII_FRAMES.len()],
+        (State::Finished, true) => "✔",
+        (State::Finished, false) => "+",
+        (State::Failed, true) => "✖",
+        (State::Failed, false) => "x",
+    };
+    StyledString::styled(state.style(), frame)
+}
+
+/// Replace `{field}` placeholders in a template. Unknown fields are kept as they are, and
+/// trailing spaces left by empty fields are removed.
+fn render_template(template: &str, field: impl Fn(&str) -> Option<StyledString>) -> StyledString {
+    let mut line = StyledString::new();
+    let mut rest = template;
+    while let Some(open) = rest.find('{') {
+        let Some(close) = rest[open..].find('}') else {
+            break;
+        };
+        line.push_str(&rest[..open]);
+        let name = &rest[open + 1..open + close];
+        match field(name) {
+            Some(value) => line.append(value),
+            None => line.push_str(&rest[open..=open + close]),
+        }
+        rest = &rest[open + close + 1..];
+    }
+    line.push_str(rest);
+
+    let mut spans = line.into_spans();
+    while let Some(last) = spans.last_mut() {
+        let trimmed = last.text.trim_end().len();
+        last.text.truncate(trimmed);
+        if !last.text.is_empty() {
+            break;
+        }
+        spans.pop();
+    }
+    spans.into_iter().collect()
+}
+
+fn percent(pos: u64, len: u64) -> u64 {
+    if len == 0 {
+        0
+    } else {
+        (pos as u128 * 100 / len as u128) as u64
+    }
+}
+
+fn eta(pos: u64, len: u64, elapsed: Duration) -> String {
+    if pos == 0 || elapsed.is_zero() {
+        return String::from("-");
+    }
+    let remaining = elapsed.as_secs_f64() * len.saturating_sub(pos) as f64 / pos as f64;
+    // The estimate can be too far away for a Duration early on with a huge length.
+    match Duration::try_from_secs_f64(remaining) {
+        Ok(remaining) => format_duration(remaining),
+        Err(_) => String::from("-"),
+    }
+}
+
+fn per_sec(pos: u64, elapsed: Duration) -> String {
+    if elapsed.is_zero() {
+        return String::from("-");
+    }
+    format!("{:.1}/s", pos as f64 / elapsed.as_secs_f64())
+}
+
+/// Format a duration as `42s`, `3m05s` or `1h02m`.
+fn format_duration(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+    if hours > 0 {
+        format!("{hours}h{minutes:02}m")
+    } else if minutes > 0 {
+        format!("{minutes}m{seconds:02}s")
+    } else {
+        format!("{seconds}s")
+    }
+}
@@ -0,0 +1,416 @@
+//! Style numbers by value, using thresholds, a continuous colour gradient or a colour scale.
+//!
+//! ```rust
+//! use rustyhues::env::PredefinedStyleType;
+//! use rustyhues::scale::{StylizeNumber, Thresholds};
+//!
+//! let latency = Thresholds::new()
+//!     .below(100.0, PredefinedStyleType::Success)
+//!     .below(500.0, PredefinedStyleType::Warning)
+//!     .otherwise(PredefinedStyleType::Error);
+//! println!("{}ms", 250.scaled(&latency));
+//! ```
+use crate::{
+    paint::Paint,
+    style::{rgb, AdaptiveColor, Color, Style, StyleColor, RGB},
+};
+
+/// Scale
+///
+/// Anything that picks a `Style` for a number.
+pub trait Scale {
+    /// Get the Style for a value.
+    fn style_for(&self, value: f64) -> Style;
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Bound {
+    Below(f64),
+    AtMost(f64),
+}
+
+impl Bound {
+    fn contains(self, value: f64) -> bool {
+        match self {
+            Bound::Below(limit) => value < limit,
+            Bound::AtMost(limit) => value <= limit,
+        }
+    }
+}
+
+/// Thresholds
+///
+/// Styles for ranges of numbers. Bounds are checked in the order they were added and the first
+/// one containing the value wins, so they are usually added from low to high. Styles can be
+/// given as a `Style` or a `PredefinedStyleType`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Thresholds {
+    bounds: Vec<(Bound, Style)>,
+    otherwise: Style,
+}
+
+impl Thresholds {
+    /// Create new, empty Thresholds. Every value gets a plain Style until bounds are added.
+    pub fn new() -> Self {
+        Self {
+            bounds: Vec::new(),
+            otherwise: Style::new(),
+        }
+    }
+
+    /// Use `style` for values less than `limit`.
+    pub fn below(mut self, limit: f64, style: impl Into<Style>) -> Self {
+        self.bounds.push((Bound::Below(limit), style.into()));
+        self
+    }
+
+    /// Use `style` for values less than or equal to `limit`.
+    pub fn at_most(mut self, limit: f64, style: impl Into<Style>) -> Self {
+        self.bounds.push((Bound::AtMost(limit), style.into()));
+        self
+    }
+
+    /// Use `style` for values not contained in any bound, including NaN.
+    pub fn otherwise(mut self, style: impl Into<Style>) -> Self {
+        self.otherwise = style.into();
+        self
+    }
+}
+
+impl Scale for Thresholds {
+    fn style_for(&self, value: f64) -> Style {
+        self.bounds
+            .iter()
+            .find(|(bound, _)| bound.contains(value))
+            .map_or(self.otherwise, |(_, style)| *style)
+    }
+}
+
+/// Gradient
+///
+/// A continuous scale interpolating an RGB colour between stops. Values outside the stops get
+/// the colour of the nearest one. When true colour isn't allowed, `Paint` falls back to the
+/// closest ANSI colour as usual.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Gradient {
+    stops: Vec<(f64, RGB)>,
+    background: bool,
+}
+
+impl Gradient {
+    /// Create a new Gradient between two colours.
+    pub fn new(start: (f64, RGB), end: (f64, RGB)) -> Self {
+        Self {
+            stops: Vec::new(),
+            background: false,
+        }
+        .stop(start.0, start.1)
+        .stop(end.0, end.1)
+    }
+
+    /// Add a colour stop.
+    pub fn stop(mut self, value: f64, color: RGB) -> Self {
+        let index = self.stops.partition_point(|(stop, _)| *stop <= value);
+        self.stops.insert(index, (value, color));
+        self
+    }
+
+    /// Colour the background instead of the foreground, for example for heatmap cells.
+    pub fn background(mut self, background: bool) -> Self {
+        self.background = background;
+        self
+    }
+
+    /// Get the interpolated colour for a value. NaN gets the colour of the first stop.
+    pub fn color_at(&self, value: f64) -> RGB {
+        let (Some(first), Some(last)) = (self.stops.first(), self.stops.last()) else {
+            return RGB::new(0, 0, 0);
+        };
+        if value.is_nan() || value <= first.0 {
+            return first.1;
+        }
+        if value >= last.0 {
+            return last.1;
+        }
+        let index = self.stops.partition_point(|(stop, _)| *stop <= value);
+        let (start, from) = self.stops[index - 1];
+        let (end, to) = self.stops[index];
+        lerp(from, to, (value - start) / (end - start))
+    }
+}
+
+impl Scale for Gradient {
+    fn style_for(&self, value: f64) -> Style {
+        let color = self.color_at(value);
+        if self.background {
+            Style::new().set_bg_rgb(color)
+        } else {
+            Style::new().set_rgb(color)
+        }
+    }
+}
+
+/// Color Scale
+///
+/// Built-in continuous colour maps for heatmaps and histograms. Viridis, magma, inferno and
+/// cividis are perceptually uniform; red-blue is diverging, with white in the middle.
+///
+/// Each map also has pre-quantized bands for the 256 and 16 colour palettes, picked so that
+/// neighbouring bands stay distinguishable when true colour isn't allowed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ColorScale {
+    Viridis,
+    Magma,
+    Inferno,
+    Cividis,
+    RedBlue,
+}
+
+impl ColorScale {
+    fn stops(self) -> &'static [RGB] {
+        match self {
+            ColorScale::Viridis => &VIRIDIS,
+            ColorScale::Magma => &MAGMA,
+            ColorScale::Inferno => &INFERNO,
+            ColorScale::Cividis => &CIVIDIS,
+            ColorScale::RedBlue => &RED_BLUE,
+        }
+    }
+
+    /// Get the colour at `t`, from 0 to 1. Values outside are clamped and NaN counts as 0.
+    pub fn sample(self, t: f64) -> RGB {
+        let stops = self.stops();
+        let position = clamp_unit(t) * (stops.len() - 1) as f64;
+        let index = (position as usize).min(stops.len() - 2);
+        lerp(stops[index], stops[index + 1], position - index as f64)
+    }
+
+    /// Get the 256 colour palette entry for the band containing `t`.
+    pub fn sample_fixed(self, t: f64) -> u8 {
+        let bands: &[u8] = match self {
+            ColorScale::Viridis => &VIRIDIS_FIXED,
+            ColorScale::Magma => &MAGMA_FIXED,
+            ColorScale::Inferno => &INFERNO_FIXED,
+            ColorScale::Cividis => &CIVIDIS_FIXED,
+            ColorScale::RedBlue => &RED_BLUE_FIXED,
+        };
+        bands[band(t, bands.len())]
+    }
+
+    /// Get the ANSI colour for the band containing `t`.
+    pub fn sample_ansi(self, t: f64) -> Color {
+        let bands: &[Color] = match self {
+            ColorScale::Viridis => &VIRIDIS_ANSI,
+            ColorScale::Magma => &MAGMA_ANSI,
+            ColorScale::Inferno => &INFERNO_ANSI,
+            ColorScale::Cividis => &CIVIDIS_ANSI,
+            ColorScale::RedBlue => &RED_BLUE_ANSI,
+        };
+        bands[band(t, bands.len())]
+    }
+
+    /// Get a Style with the foreground colour at `t`. When it is displayed, the RGB colour is
+    /// used at `ColorLevel::TrueColor`, the pre-quantized 256 colour band at
+    /// `ColorLevel::Ansi256`, and the ANSI band otherwise.
+    pub fn style(self, t: f64) -> Style {
+        let mut style = Style::new();
+        style.fg = Some(self.style_color(t));
+        style
+    }
+
+    /// Get a Style with the background colour at `t`, for example for heatmap cells.
+    pub fn bg_style(self, t: f64) -> Style {
+        let mut style = Style::new();
+        style.bg = Some(self.style_color(t));
+        style
+    }
+
+    fn style_color(self, t: f64) -> StyleColor {
+        StyleColor::Adaptive(AdaptiveColor {
+            rgb: self.sample(t),
+            fixed: self.sample_fixed(t),
+            named: self.sample_ansi(t),
+        })
+    }
+}
+
+impl Scale for ColorScale {
+    /// Values are expected from 0 to 1.
+    fn style_for(&self, value: f64) -> Style {
+        self.style(value)
+    }
+}
+
+fn clamp_unit(t: f64) -> f64 {
+    if t.is_nan() {
+        0.0
+    } else {
+        t.clamp(0.0, 1.0)
+    }
+}
+
+/// Get the index of the band containing `t`, out of `bands` equal bands.
+fn band(t: f64, bands: usize) -> usize {
+    ((clamp_unit(t) * bands as f64) as usize).min(bands - 1)
+}
+
+const VIRIDIS: [RGB; 9] = [
+    rgb(0x44, 0x01, 0x54),
+    rgb(0x47, 0x2d, 0x7b),
+    rgb(0x3b, 0x52, 0x8b),
+    rgb(0x2c, 0x72, 0x8e),
+    rgb(0x21, 0x91, 0x8c),
+    rgb(0x28, 0xae, 0x80),
+    rgb(0x5e, 0xc9, 0x62),
+    rgb(0xad, 0xdc, 0x30),
+    rgb(0xfd, 0xe7, 0x25),
+];
+
+const MAGMA: [RGB; 9] = [
+    rgb(0x00, 0x00, 0x04),
+    rgb(0x1c, 0x10, 0x44),
+    rgb(0x4f, 0x12, 0x7b),
+    rgb(0x81, 0x25, 0x81),
+    rgb(0xb5, 0x36, 0x7a),
+    rgb(0xe5, 0x50, 0x64),
+    rgb(0xfb, 0x87, 0x61),
+    rgb(0xfe, 0xc2, 0x87),
+    rgb(0xfc, 0xfd, 0xbf),
+];
+
+const INFERNO: [RGB; 9] = [
+    rgb(0x00, 0x00, 0x04),
+    rgb(0x1f, 0x0c, 0x48),
+    rgb(0x55, 0x0f, 0x6d),
+    rgb(0x88, 0x22, 0x6a),
+    rgb(0xba, 0x36, 0x55),
+    rgb(0xe3, 0x59, 0x33),
+    rgb(0xf9, 0x8c, 0x0a),
+    rgb(0xf9, 0xc9, 0x32),
+    rgb(0xfc, 0xff, 0xa4),
+];
+
+const CIVIDIS: [RGB; 9] = [
+    rgb(0x00, 0x22, 0x4e),
+    rgb(0x12, 0x35, 0x70),
+    rgb(0x3b, 0x49, 0x6c),
+    rgb(0x57, 0x5d, 0x6d),
+    rgb(0x70, 0x71, 0x73),
+    rgb(0x8a, 0x87, 0x79),
+    rgb(0xa6, 0x9d, 0x75),
+    rgb(0xc4, 0xb5, 0x6c),
+    rgb(0xfe, 0xe8, 0x38),
+];
+
+const RED_BLUE: [RGB; 9] = [
+    rgb(0xb2, 0x18, 0x2b),
+    rgb(0xd6, 0x60, 0x4d),
+    rgb(0xf4, 0xa5, 0x82),
+    rgb(0xfd, 0xdb, 0xc7),
+    rgb(0xf7, 0xf7, 0xf7),
+    rgb(0xd1, 0xe5, 0xf0),
+    rgb(0x92, 0xc5, 0xde),
+    rgb(0x43, 0x93, 0xc3),
+    rgb(0x21, 0x66, 0xac),
+];
+
+const VIRIDIS_FIXED: [u8; 11] = [53, 60, 24, 30, 36, 72, 71, 77, 113, 148, 184];
+
+const MAGMA_FIXED: [u8; 12] = [232, 234, 17, 53, 54, 90, 132, 167, 209, 216, 222, 229];
+
+const INFERNO_FIXED: [u8; 12] = [232, 234, 17, 53, 89, 131, 167, 166, 208, 214, 221, 228];
+
+const CIVIDIS_FIXED: [u8; 14] = [
+    17, 23, 239, 240, 59, 241, 242, 243, 244, 102, 138, 143, 179, 221,
+];
+
+const RED_BLUE_FIXED: [u8; 14] = [
+    125, 167, 210, 216, 223, 224, 255, 254, 153, 152, 110, 74, 67, 25,
+];
+
+const VIRIDIS_ANSI: [Color; 6] = [
+    Color::Magenta,
+    Color::Blue,
+    Color::Cyan,
+    Color::Green,
+    Color::BrightGreen,
+    Color::BrightYellow,
+];
+
+const MAGMA_ANSI: [Color; 6] = [
+    Color::Black,
+    Color::Magenta,
+    Color::BrightMagenta,
+    Color::BrightRed,
+    Color::BrightYellow,
+    Color::BrightWhite,
+];
+
+const INFERNO_ANSI: [Color; 6] = [
+    Color::Black,
+    Color::Magenta,
+    Color::Red,
+    Color::BrightRed,
+    Color::Yellow,
+    Color::BrightYellow,
+];
+
+const CIVIDIS_ANSI: [Color; 5] = [
+    Color::Blue,
+    Color::BrightBlack,
+    Color::White,
+    Color::Yellow,
+    Color::BrightYellow,
+];
+
+const RED_BLUE_ANSI: [Color; 5] = [
+    Color::Red,
+    Color::BrightRed,
+    Color::White,
+    Color::BrightBlue,
+    Color::Blue,
+];
+
+/// Interpolate between two colours, with `t` from 0 to 1.
+pub(crate) fn lerp(from: RGB, to: RGB, t: f64) -> RGB {
+    let channel = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * t).round() as u8;
+    RGB::new(
+        channel(from.r, to.r),
+        channel(from.g, to.g),
+        channel(from.b, to.b),
+    )
+}
+
+/// Stylize Number
+///
+/// Style numbers directly with a `Scale`.
+pub trait StylizeNumber: Sized {
+    /// Get the value used to pick a Style.
+    fn scale_value(&self) -> f64;
+
+    /// Create a new std_out `Paint` context styled by a Scale.
+    fn scaled<S: Scale + ?Sized>(self, scale: &S) -> Paint<Self> {
+        let style = scale.style_for(self.scale_value());
+        Paint::new_stdout(self).style(style)
+    }
+
+    /// Create a new std_err `Paint` context styled by a Scale.
+    fn scaled_err<S: Scale + ?Sized>(self, scale: &S) -> Paint<Self> {
+        let style = scale.style_for(self.scale_value());
+        Paint::new_stderr(self).style(style)
+    }
+}
+
+macro_rules! impl_stylize_number {
+    ($($number:ty),*) => {
+        $(
+            impl StylizeNumber for $number {
+                fn scale_value(&self) -> f64 {
+                    *self as f64
+                }
+            }
+        )*
+    };
+}
+
+impl_stylize_number!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64);
@@ -1,5 +1,18 @@
-#[derive(Clone, Copy, Debug)]
+#[cfg(unix)]
+use std::os::unix::io::RawFd;
+
+/// Stream
+///
+/// The output stream styled content is written to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Stream {
     Stdout,
     Stderr,
+    /// Any open file descriptor, for example `/dev/tty`, a pipe to a child process or a log
+    /// file. Colour support is detected the same way as for stdout and stderr.
+    #[cfg(unix)]
+    Fd(RawFd),
+    /// A destination without a file descriptor, such as an in-memory buffer. The value says
+    /// whether it supports colour, and takes the place of TTY detection.
+    Custom(bool),
 }
@@ -1,3 +1,7 @@
+use crate::{
+    env::{predefined_style, PredefinedStyle, PredefinedStyleType},
+    Paint,
+};
 use core::fmt;
 
 pub const COLOR_BLACK_CODE: &str = "30";
@@ -36,6 +40,8 @@ pub const COLOR_BG_BRIGHT_WHITE_CODE: &str = "107";
 
 pub const COLOR_RGB: &str = "38;2;";
 pub const COLOR_BG_RGB: &str = "48;2;";
+pub const COLOR_FIXED: &str = "38;5;";
+pub const COLOR_BG_FIXED: &str = "48;5;";
 
 pub const DECORATION_BOLD_CODE: &str = "1";
 pub const DECORATION_DIM_CODE: &str = "2";
@@ -43,23 +49,24 @@ pub const DECORATION_ITALIC_CODE: &str = "3";
 pub const DECORATION_UNDERLINE_CODE: &str = "4";
 pub const DECORATION_INVERT_CODE: &str = "7";
 
-const RGB_COLORS: [((u8, u8, u8), Color); 16] = [
-    ((0, 0, 0), Color::Black),
-    ((205, 49, 49), Color::Red),
-    ((13, 188, 121), Color::Green),
-    ((229, 229, 16), Color::Yellow),
-    ((36, 114, 200), Color::Blue),
-    ((188, 63, 188), Color::Magenta),
-    ((17, 168, 205), Color::Cyan),
-    ((229, 229, 229), Color::White),
-    ((102, 102, 102), Color::BrightBlack),
-    ((241, 76, 76), Color::BrightRed),
-    ((35, 209, 139), Color::BrightGreen),
-    ((245, 245, 67), Color::BrightYellow),
-    ((59, 142, 234), Color::BrightBlue),
-    ((214, 112, 214), Color::BrightMagenta),
-    ((41, 184, 219), Color::BrightCyan),
-    ((255, 255, 255), Color::BrightWhite),
+/// ANSI colours in palette order, so that `ANSI_COLORS[n]` is palette entry `n`.
+pub(crate) const ANSI_COLORS: [Color; 16] = [
+    Color::Black,
+    Color::Red,
+    Color::Green,
+    Color::Yellow,
+    Color::Blue,
+    Color::Magenta,
+    Color::Cyan,
+    Color::White,
+    Color::BrightBlack,
+    Color::BrightRed,
+    Color::BrightGreen,
+    Color::BrightYellow,
+    Color::BrightBlue,
+    Color::BrightMagenta,
+    Color::BrightCyan,
+    Color::BrightWhite,
 ];
 
 /// Color
@@ -129,6 +136,28 @@ impl Color {
             Color::BrightWhite => COLOR_BG_BRIGHT_WHITE_CODE,
         }
     }
+
+    /// Get the position of a particular ANSI colour in the 16 colour palette.
+    pub const fn index(self) -> u8 {
+        match self {
+            Color::Black => 0,
+            Color::Red => 1,
+            Color::Green => 2,
+            Color::Yellow => 3,
+            Color::Blue => 4,
+            Color::Magenta => 5,
+            Color::Cyan => 6,
+            Color::White => 7,
+            Color::BrightBlack => 8,
+            Color::BrightRed => 9,
+            Color::BrightGreen => 10,
+            Color::BrightYellow => 11,
+            Color::BrightBlue => 12,
+            Color::BrightMagenta => 13,
+            Color::BrightCyan => 14,
+            Color::BrightWhite => 15,
+        }
+    }
 }
 
 /// Decoration
@@ -154,8 +183,26 @@ impl Decoration {
             Decoration::Invert => DECORATION_INVERT_CODE,
         }
     }
+
+    const fn bit(self) -> u8 {
+        match self {
+            Decoration::Bold => 1,
+            Decoration::Dim => 1 << 1,
+            Decoration::Italic => 1 << 2,
+            Decoration::Underline => 1 << 3,
+            Decoration::Invert => 1 << 4,
+        }
+    }
 }
 
+pub(crate) const DECORATIONS: [Decoration; 5] = [
+    Decoration::Bold,
+    Decoration::Dim,
+    Decoration::Italic,
+    Decoration::Underline,
+    Decoration::Invert,
+];
+
 /// RGB
 ///
 /// Struct containing methods for RGB and Hex values.
@@ -206,13 +253,18 @@ impl RGB {
         write!(f, "{};{};{}", self.r, self.g, self.b)
     }
 
+    /// Format the RGB value as a lowercase hex string with a leading `#`, for example `#ff8800`.
+    pub fn to_hex(self) -> String {
+        format!("#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+    }
+
     /// Work out the closest ANSI colour to an RGB value for use if terminal doesn't support RGB.
     pub fn closest_color(self) -> Option<Color> {
         let mut closest_color: Option<Color> = None;
         let mut closest_distance: i32 = i32::MAX;
 
-        for (rgb, color) in RGB_COLORS {
-            let (r, g, b) = rgb;
+        for (color, rgb) in ANSI_COLORS.into_iter().zip(Palette::VSCODE.colors) {
+            let RGB { r, g, b } = rgb;
             let dr = (self.r as i32 - r as i32).pow(2);
             let dg = (self.g as i32 - g as i32).pow(2);
             let db = (self.b as i32 - b as i32).pow(2);
@@ -225,6 +277,27 @@ impl RGB {
 
         closest_color
     }
+
+    /// Work out the closest entry of the 256 colour palette to an RGB value. Only the colour
+    /// cube and grayscale ramp are considered, since entries 0 to 15 depend on the terminal theme.
+    pub fn closest_fixed(self) -> u8 {
+        let mut closest_index = 16;
+        let mut closest_distance = i32::MAX;
+
+        for index in 16..=255 {
+            let RGB { r, g, b } = Palette::VSCODE.fixed(index);
+            let dr = (self.r as i32 - r as i32).pow(2);
+            let dg = (self.g as i32 - g as i32).pow(2);
+            let db = (self.b as i32 - b as i32).pow(2);
+            let d = dr + dg + db;
+            if d < closest_distance {
+                closest_index = index;
+                closest_distance = d;
+            }
+        }
+
+        closest_index
+    }
 }
 
 fn validate_hex_value(hex: &str) -> Option<&str> {
@@ -249,3 +322,542 @@ pub enum Comparison {
     Is,
     Not,
 }
+
+/// Style Color
+///
+/// Any colour a `Style` can hold: a named ANSI colour, an entry of the 256 colour palette, an RGB value or an `AdaptiveColor`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StyleColor {
+    Named(Color),
+    Fixed(u8),
+    Rgb(RGB),
+    Adaptive(AdaptiveColor),
+}
+
+/// Adaptive Color
+///
+/// An RGB colour along with the 256 colour palette entry and ANSI colour picked for it in
+/// advance. `Paint` writes whichever of them the colour level supports, rather than working
+/// out the closest colour itself.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AdaptiveColor {
+    pub rgb: RGB,
+    pub fixed: u8,
+    pub named: Color,
+}
+
+impl StyleColor {
+    fn write_fg_code<W: fmt::Write>(self, w: &mut W) -> fmt::Result {
+        match self {
+            StyleColor::Named(color) => w.write_str(color.fg_code()),
+            StyleColor::Fixed(index) => write!(w, "{COLOR_FIXED}{index}"),
+            StyleColor::Rgb(rgb) | StyleColor::Adaptive(AdaptiveColor { rgb, .. }) => {
+                write!(w, "{COLOR_RGB}{};{};{}", rgb.r, rgb.g, rgb.b)
+            }
+        }
+    }
+
+    fn write_bg_code<W: fmt::Write>(self, w: &mut W) -> fmt::Result {
+        match self {
+            StyleColor::Named(color) => w.write_str(color.bg_code()),
+            StyleColor::Fixed(index) => write!(w, "{COLOR_BG_FIXED}{index}"),
+            StyleColor::Rgb(rgb) | StyleColor::Adaptive(AdaptiveColor { rgb, .. }) => {
+                write!(w, "{COLOR_BG_RGB}{};{};{}", rgb.r, rgb.g, rgb.b)
+            }
+        }
+    }
+}
+
+impl From<Color> for StyleColor {
+    fn from(color: Color) -> Self {
+        StyleColor::Named(color)
+    }
+}
+
+impl From<RGB> for StyleColor {
+    fn from(rgb: RGB) -> Self {
+        StyleColor::Rgb(rgb)
+    }
+}
+
+/// Style
+///
+/// A reusable set of colours and decorations that can be applied to any content.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Style {
+    pub fg: Option<StyleColor>,
+    pub bg: Option<StyleColor>,
+    pub(crate) decorations: u8,
+}
+
+impl Style {
+    /// Create a new Style with no colours or decorations.
+    pub const fn new() -> Self {
+        Self {
+            fg: None,
+            bg: None,
+            decorations: 0,
+        }
+    }
+
+    /// Create a new Style from one of the predefined styles.
+    pub fn predefined(style_type: PredefinedStyleType) -> Self {
+        Self::from(predefined_style(style_type))
+    }
+
+    /// Set the foreground colour.
+    pub fn fg(mut self, color: Color) -> Self {
+        self.fg = Some(StyleColor::Named(color));
+        self
+    }
+
+    /// Set the background colour.
+    pub fn bg(mut self, color: Color) -> Self {
+        self.bg = Some(StyleColor::Named(color));
+        self
+    }
+
+    /// Set the foreground RGB value.
+    pub fn set_rgb(mut self, rgb_value: RGB) -> Self {
+        self.fg = Some(StyleColor::Rgb(rgb_value));
+        self
+    }
+
+    /// Set the background RGB value.
+    pub fn set_bg_rgb(mut self, rgb_value: RGB) -> Self {
+        self.bg = Some(StyleColor::Rgb(rgb_value));
+        self
+    }
+
+    /// Set the foreground colour using an RGB value.
+    pub fn rgb(self, r: u8, g: u8, b: u8) -> Self {
+        self.set_rgb(RGB::new(r, g, b))
+    }
+
+    /// Set the background colour using an RGB value.
+    pub fn bg_rgb(self, r: u8, g: u8, b: u8) -> Self {
+        self.set_bg_rgb(RGB::new(r, g, b))
+    }
+
+    /// Set the foreground colour to an entry of the 256 colour palette.
+    pub fn fixed(mut self, index: u8) -> Self {
+        self.fg = Some(StyleColor::Fixed(index));
+        self
+    }
+
+    /// Set the background colour to an entry of the 256 colour palette.
+    pub fn bg_fixed(mut self, index: u8) -> Self {
+        self.bg = Some(StyleColor::Fixed(index));
+        self
+    }
+
+    /// Set the foreground colour to ANSI Black.
+    pub fn black(self) -> Self {
+        self.fg(Color::Black)
+    }
+
+    /// Set the foreground colour to ANSI Red.
+    pub fn red(self) -> Self {
+        self.fg(Color::Red)
+    }
+
+    /// Set the foreground colour to ANSI Green.
+    pub fn green(self) -> Self {
+        self.fg(Color::Green)
+    }
+
+    /// Set the foreground colour to ANSI Yellow.
+    pub fn yellow(self) -> Self {
+        self.fg(Color::Yellow)
+    }
+
+    /// Set the foreground colour to ANSI Blue.
+    pub fn blue(self) -> Self {
+        self.fg(Color::Blue)
+    }
+
+    /// Set the foreground colour to ANSI Magenta.
+    pub fn magenta(self) -> Self {
+        self.fg(Color::Magenta)
+    }
+
+    /// Set the foreground colour to ANSI Cyan.
+    pub fn cyan(self) -> Self {
+        self.fg(Color::Cyan)
+    }
+
+    /// Set the foreground colour to ANSI White.
+    pub fn white(self) -> Self {
+        self.fg(Color::White)
+    }
+
+    /// Set the foreground colour to ANSI Bright Black.
+    pub fn bright_black(self) -> Self {
+        self.fg(Color::BrightBlack)
+    }
+
+    /// Set the foreground colour to ANSI Bright Red.
+    pub fn bright_red(self) -> Self {
+        self.fg(Color::BrightRed)
+    }
+
+    /// Set the foreground colour to ANSI Bright Green.
+    pub fn bright_green(self) -> Self {
+        self.fg(Color::BrightGreen)
+    }
+
+    /// Set the foreground colour to ANSI Bright Yellow.
+    pub fn bright_yellow(self) -> Self {
+        self.fg(Color::BrightYellow)
+    }
+
+    /// Set the foreground colour to ANSI Bright Blue.
+    pub fn bright_blue(self) -> Self {
+        self.fg(Color::BrightBlue)
+    }
+
+    /// Set the foreground colour to ANSI Bright Magenta.
+    pub fn bright_magenta(self) -> Self {
+        self.fg(Color::BrightMagenta)
+    }
+
+    /// Set the foreground colour to ANSI Bright Cyan.
+    pub fn bright_cyan(self) -> Self {
+        self.fg(Color::BrightCyan)
+    }
+
+    /// Set the foreground colour to ANSI Bright White.
+    pub fn bright_white(self) -> Self {
+        self.fg(Color::BrightWhite)
+    }
+
+    /// Set the background colour to ANSI Black.
+    pub fn bg_black(self) -> Self {
+        self.bg(Color::Black)
+    }
+
+    /// Set the background colour to ANSI Red.
+    pub fn bg_red(self) -> Self {
+        self.bg(Color::Red)
+    }
+
+    /// Set the background colour to ANSI Green.
+    pub fn bg_green(self) -> Self {
+        self.bg(Color::Green)
+    }
+
+    /// Set the background colour to ANSI Yellow.
+    pub fn bg_yellow(self) -> Self {
+        self.bg(Color::Yellow)
+    }
+
+    /// Set the background colour to ANSI Blue.
+    pub fn bg_blue(self) -> Self {
+        self.bg(Color::Blue)
+    }
+
+    /// Set the background colour to ANSI Magenta.
+    pub fn bg_magenta(self) -> Self {
+        self.bg(Color::Magenta)
+    }
+
+    /// Set the background colour to ANSI Cyan.
+    pub fn bg_cyan(self) -> Self {
+        self.bg(Color::Cyan)
+    }
+
+    /// Set the background colour to ANSI White.
+    pub fn bg_white(self) -> Self {
+        self.bg(Color::White)
+    }
+
+    /// Set the background colour to ANSI Bright Black.
+    pub fn bg_bright_black(self) -> Self {
+        self.bg(Color::BrightBlack)
+    }
+
+    /// Set the background colour to ANSI Bright Red.
+    pub fn bg_bright_red(self) -> Self {
+        self.bg(Color::BrightRed)
+    }
+
+    /// Set the background colour to ANSI Bright Green.
+    pub fn bg_bright_green(self) -> Self {
+        self.bg(Color::BrightGreen)
+    }
+
+    /// Set the background colour to ANSI Bright Yellow.
+    pub fn bg_bright_yellow(self) -> Self {
+        self.bg(Color::BrightYellow)
+    }
+
+    /// Set the background colour to ANSI Bright Blue.
+    pub fn bg_bright_blue(self) -> Self {
+        self.bg(Color::BrightBlue)
+    }
+
+    /// Set the background colour to ANSI Bright Magenta.
+    pub fn bg_bright_magenta(self) -> Self {
+        self.bg(Color::BrightMagenta)
+    }
+
+    /// Set the background colour to ANSI Bright Cyan.
+    pub fn bg_bright_cyan(self) -> Self {
+        self.bg(Color::BrightCyan)
+    }
+
+    /// Set the background colour to ANSI Bright White.
+    pub fn bg_bright_white(self) -> Self {
+        self.bg(Color::BrightWhite)
+    }
+
+    /// Add the Bold decoration.
+    pub fn bold(self) -> Self {
+        self.decoration(Decoration::Bold)
+    }
+
+    /// Add the Dim decoration.
+    pub fn dim(self) -> Self {
+        self.decoration(Decoration::Dim)
+    }
+
+    /// Add the Italic decoration.
+    pub fn italic(self) -> Self {
+        self.decoration(Decoration::Italic)
+    }
+
+    /// Add the Underline decoration.
+    pub fn underline(self) -> Self {
+        self.decoration(Decoration::Underline)
+    }
+
+    /// Add the Invert decoration.
+    pub fn invert(self) -> Self {
+        self.decoration(Decoration::Invert)
+    }
+
+    /// Add a decoration.
+    pub fn decoration(mut self, decoration: Decoration) -> Self {
+        self.decorations |= decoration.bit();
+        self
+    }
+
+    /// Remove a decoration.
+    pub fn remove_decoration(mut self, decoration: Decoration) -> Self {
+        self.decorations &= !decoration.bit();
+        self
+    }
+
+    /// Check whether a decoration is set.
+    pub fn has_decoration(&self, decoration: Decoration) -> bool {
+        self.decorations & decoration.bit() != 0
+    }
+
+    /// Get the decorations that are set, in SGR code order.
+    pub fn decorations(&self) -> impl Iterator<Item = Decoration> + '_ {
+        DECORATIONS
+            .into_iter()
+            .filter(|decoration| self.has_decoration(*decoration))
+    }
+
+    /// Put `top` over this Style. Colours `top` sets replace the ones here, colours it doesn't
+    /// set are kept and the decorations of both are combined.
+    pub fn patch(self, top: Style) -> Self {
+        Self {
+            fg: top.fg.or(self.fg),
+            bg: top.bg.or(self.bg),
+            decorations: self.decorations | top.decorations,
+        }
+    }
+
+    /// Check whether the Style has no colours or decorations.
+    pub fn is_plain(&self) -> bool {
+        self.fg.is_none() && self.bg.is_none() && self.decorations == 0
+    }
+
+    /// Create a new `Paint` context that writes to std_out and apply this Style to it.
+    pub fn paint<T>(&self, inner: T) -> Paint<T> {
+        Paint::new_stdout(inner).style(*self)
+    }
+
+    /// Create a new `Paint` context that writes to std_err and apply this Style to it.
+    pub fn paint_err<T>(&self, inner: T) -> Paint<T> {
+        Paint::new_stderr(inner).style(*self)
+    }
+
+    /// Write the SGR sequence that switches this Style on, without checking terminal settings.
+    /// Nothing is written for a plain Style.
+    pub fn write_prefix<W: fmt::Write>(&self, w: &mut W) -> fmt::Result {
+        if self.is_plain() {
+            return Ok(());
+        }
+
+        w.write_str("\x1b[")?;
+        let mut first = true;
+        let mut separator = |w: &mut W| {
+            if first {
+                first = false;
+                Ok(())
+            } else {
+                w.write_str(";")
+            }
+        };
+        if let Some(fg) = self.fg {
+            separator(w)?;
+            fg.write_fg_code(w)?;
+        }
+        if let Some(bg) = self.bg {
+            separator(w)?;
+            bg.write_bg_code(w)?;
+        }
+        for decoration in self.decorations() {
+            separator(w)?;
+            w.write_str(decoration.code())?;
+        }
+        w.write_str("m")
+    }
+}
+
+impl From<PredefinedStyle> for Style {
+    fn from(predefined: PredefinedStyle) -> Self {
+        let mut style = Style::new();
+        style.fg = predefined.fg.map(StyleColor::Named);
+        style.bg = predefined.bg.map(StyleColor::Named);
+        if let Some(decoration) = predefined.decoration {
+            style = style.decoration(decoration);
+        }
+        style
+    }
+}
+
+impl From<PredefinedStyleType> for Style {
+    fn from(style_type: PredefinedStyleType) -> Self {
+        Style::predefined(style_type)
+    }
+}
+
+pub(crate) const fn rgb(r: u8, g: u8, b: u8) -> RGB {
+    RGB { r, g, b }
+}
+
+/// Palette
+///
+/// The RGB values used to show the 16 ANSI colours, plus the default foreground and background.
+/// Used when styled text is rendered somewhere other than a terminal.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Palette {
+    pub colors: [RGB; 16],
+    pub foreground: RGB,
+    pub background: RGB,
+}
+
+impl Palette {
+    /// The palette used by Visual Studio Code's terminal, which is also used for RGB fallback.
+    pub const VSCODE: Palette = Palette {
+        colors: [
+            rgb(0, 0, 0),
+            rgb(205, 49, 49),
+            rgb(13, 188, 121),
+            rgb(229, 229, 16),
+            rgb(36, 114, 200),
+            rgb(188, 63, 188),
+            rgb(17, 168, 205),
+            rgb(229, 229, 229),
+            rgb(102, 102, 102),
+            rgb(241, 76, 76),
+            rgb(35, 209, 139),
+            rgb(245, 245, 67),
+            rgb(59, 142, 234),
+            rgb(214, 112, 214),
+            rgb(41, 184, 219),
+            rgb(255, 255, 255),
+        ],
+        foreground: rgb(204, 204, 204),
+        background: rgb(30, 30, 30),
+    };
+
+    /// The default xterm palette.
+    pub const XTERM: Palette = Palette {
+        colors: [
+            rgb(0, 0, 0),
+            rgb(205, 0, 0),
+            rgb(0, 205, 0),
+            rgb(205, 205, 0),
+            rgb(0, 0, 238),
+            rgb(205, 0, 205),
+            rgb(0, 205, 205),
+            rgb(229, 229, 229),
+            rgb(127, 127, 127),
+            rgb(255, 0, 0),
+            rgb(0, 255, 0),
+            rgb(255, 255, 0),
+            rgb(92, 92, 255),
+            rgb(255, 0, 255),
+            rgb(0, 255, 255),
+            rgb(255, 255, 255),
+        ],
+        foreground: rgb(229, 229, 229),
+        background: rgb(0, 0, 0),
+    };
+
+    /// The Campbell palette used by Windows Terminal.
+    pub const CAMPBELL: Palette = Palette {
+        colors: [
+            rgb(12, 12, 12),
+            rgb(197, 15, 31),
+            rgb(19, 161, 14),
+            rgb(193, 156, 0),
+            rgb(0, 55, 218),
+            rgb(136, 23, 152),
+            rgb(58, 150, 221),
+            rgb(204, 204, 204),
+            rgb(118, 118, 118),
+            rgb(231, 72, 86),
+            rgb(22, 198, 12),
+            rgb(249, 241, 165),
+            rgb(59, 120, 255),
+            rgb(180, 0, 158),
+            rgb(97, 214, 214),
+            rgb(242, 242, 242),
+        ],
+        foreground: rgb(204, 204, 204),
+        background: rgb(12, 12, 12),
+    };
+
+    /// Get the RGB value of an ANSI colour.
+    pub fn color(&self, color: Color) -> RGB {
+        self.colors[color.index() as usize]
+    }
+
+    /// Get the RGB value of an entry in the 256 colour palette. Entries 0 to 15 come from
+    /// this palette, the rest are the standard 6x6x6 colour cube and grayscale ramp.
+    pub fn fixed(&self, index: u8) -> RGB {
+        match index {
+            0..=15 => self.colors[index as usize],
+            16..=231 => {
+                let cube = |level: u8| if level == 0 { 0 } else { 55 + level * 40 };
+                let index = index - 16;
+                rgb(cube(index / 36), cube(index / 6 % 6), cube(index % 6))
+            }
+            _ => {
+                let level = 8 + (index - 232) * 10;
+                rgb(level, level, level)
+            }
+        }
+    }
+
+    /// Get the RGB value of any colour a `Style` can hold.
+    pub fn resolve(&self, color: StyleColor) -> RGB {
+        match color {
+            StyleColor::Named(color) => self.color(color),
+            StyleColor::Fixed(index) => self.fixed(index),
+            StyleColor::Rgb(rgb) | StyleColor::Adaptive(AdaptiveColor { rgb, .. }) => rgb,
+        }
+    }
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Palette::VSCODE
+    }
+}
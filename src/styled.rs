@@ -0,0 +1,200 @@
+use crate::{ansi::plain_width, style::Style, Paint};
+use core::fmt;
+
+/// Span
+///
+/// A run of text that shares a single `Style`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Span {
+    pub style: Style,
+    pub text: String,
+}
+
+impl Span {
+    /// Create a new Span.
+    pub fn new(style: Style, text: impl Into<String>) -> Self {
+        Self {
+            style,
+            text: text.into(),
+        }
+    }
+
+    /// Create a new Span without any styles.
+    pub fn plain(text: impl Into<String>) -> Self {
+        Self::new(Style::new(), text)
+    }
+}
+
+/// StyledString
+///
+/// Text made up of differently styled spans. Displaying a StyledString follows the same
+/// terminal detection rules as `Paint`, while `to_ansi()` always includes the escape sequences.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct StyledString {
+    spans: Vec<Span>,
+}
+
+impl StyledString {
+    /// Create a new, empty StyledString.
+    pub fn new() -> Self {
+        Self { spans: Vec::new() }
+    }
+
+    /// Create a new StyledString holding a single styled span.
+    pub fn styled(style: Style, text: impl Into<String>) -> Self {
+        let mut styled = Self::new();
+        styled.push(style, text);
+        styled
+    }
+
+    /// Append styled text. Text that has the same style as the last span is merged into it
+    /// and empty text is ignored.
+    pub fn push(&mut self, style: Style, text: impl Into<String>) {
+        let text = text.into();
+        if text.is_empty() {
+            return;
+        }
+        match self.spans.last_mut() {
+            Some(last) if last.style == style => last.text.push_str(&text),
+            _ => self.spans.push(Span { style, text }),
+        }
+    }
+
+    /// Append text without any styles.
+    pub fn push_str(&mut self, text: &str) {
+        self.push(Style::new(), text);
+    }
+
+    /// Append all spans of another StyledString.
+    pub fn append(&mut self, other: StyledString) {
+        for span in other.spans {
+            self.push(span.style, span.text);
+        }
+    }
+
+    /// Get the spans making up the StyledString.
+    pub fn spans(&self) -> &[Span] {
+        &self.spans
+    }
+
+    /// Take the spans making up the StyledString.
+    pub fn into_spans(self) -> Vec<Span> {
+        self.spans
+    }
+
+    /// Check whether the StyledString has no text.
+    pub fn is_empty(&self) -> bool {
+        self.spans.is_empty()
+    }
+
+    /// Get the text without any styles.
+    pub fn plain_text(&self) -> String {
+        self.spans.iter().map(|span| span.text.as_str()).collect()
+    }
+
+    /// Work out how many terminal columns the text occupies once displayed.
+    pub fn width(&self) -> usize {
+        self.spans.iter().map(|span| plain_width(&span.text)).sum()
+    }
+
+    /// Apply a base style to every span. Colours a span already has are kept, missing colours
+    /// are taken from `base` and the decorations of `base` are added.
+    pub fn with_base_style(&self, base: Style) -> StyledString {
+        self.spans
+            .iter()
+            .map(|span| Span::new(base.patch(span.style), span.text.clone()))
+            .collect()
+    }
+
+    /// Split the StyledString on newlines, keeping the style of every span.
+    pub fn lines(&self) -> Vec<StyledString> {
+        let mut lines = vec![StyledString::new()];
+        for span in &self.spans {
+            for (i, part) in span.text.split('\n').enumerate() {
+                if i > 0 {
+                    lines.push(StyledString::new());
+                }
+                if let Some(line) = lines.last_mut() {
+                    line.push(span.style, part);
+                }
+            }
+        }
+        lines
+    }
+
+    /// Render the StyledString with escape sequences, without checking terminal settings.
+    /// Every span is reset at its end so that styles never leak into following text.
+    pub fn to_ansi(&self) -> String {
+        let mut rendered = String::new();
+        for span in &self.spans {
+            if span.style.is_plain() {
+                rendered.push_str(&span.text);
+            } else {
+                // Writing to a String cannot fail.
+                let _ = span.style.write_prefix(&mut rendered);
+                rendered.push_str(&span.text);
+                rendered.push_str("\x1b[0m");
+            }
+        }
+        rendered
+    }
+}
+
+impl fmt::Display for StyledString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for span in &self.spans {
+            write!(f, "{}", span.style.paint(span.text.as_str()))?;
+        }
+        Ok(())
+    }
+}
+
+impl From<&str> for StyledString {
+    fn from(text: &str) -> Self {
+        Self::styled(Style::new(), text)
+    }
+}
+
+impl From<String> for StyledString {
+    fn from(text: String) -> Self {
+        Self::styled(Style::new(), text)
+    }
+}
+
+impl From<Span> for StyledString {
+    fn from(span: Span) -> Self {
+        Self::styled(span.style, span.text)
+    }
+}
+
+impl<T: fmt::Display> From<Paint<T>> for StyledString {
+    /// Take the content and styles of a Paint, whether or not colours are enabled for it.
+    fn from(paint: Paint<T>) -> Self {
+        Self::styled(paint.resolved_style(), paint.inner.to_string())
+    }
+}
+
+impl FromIterator<Span> for StyledString {
+    fn from_iter<I: IntoIterator<Item = Span>>(iter: I) -> Self {
+        let mut styled = Self::new();
+        styled.extend(iter);
+        styled
+    }
+}
+
+impl Extend<Span> for StyledString {
+    fn extend<I: IntoIterator<Item = Span>>(&mut self, iter: I) {
+        for span in iter {
+            self.push(span.style, span.text);
+        }
+    }
+}
+
+impl IntoIterator for StyledString {
+    type Item = Span;
+    type IntoIter = std::vec::IntoIter<Span>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.spans.into_iter()
+    }
+}
@@ -0,0 +1,277 @@
+//! Render ANSI styled text as an SVG image of a terminal window.
+//!
+//! Output only depends on the input and the chosen options, so documentation screenshots can
+//! be regenerated deterministically and offline.
+//!
+//! ```rust
+//! use rustyhues::svg::{Chrome, Svg};
+//!
+//! let image = Svg::new()
+//!     .chrome(Chrome::Window)
+//!     .title("cargo run --example demo")
+//!     .render("\x1b[32mok\x1b[0m");
+//! assert!(image.starts_with("<svg"));
+//! ```
+use crate::{
+    ansi::{char_width, parse_ansi},
+    html,
+    style::{Decoration, Palette, Style, RGB},
+    styled::StyledString,
+};
+
+const DEFAULT_FONT_FAMILY: &str = "ui-monospace, SFMono-Regular, Menlo, Consolas, monospace";
+const CHROME_HEIGHT: f64 = 32.0;
+const BUTTON_COLORS: [&str; 3] = ["#ff5f56", "#ffbd2e", "#27c93f"];
+
+/// Chrome
+///
+/// The window decorations drawn around the terminal content.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Chrome {
+    /// Only the terminal background.
+    None,
+    /// A title bar with window buttons and an optional title.
+    #[default]
+    Window,
+}
+
+/// Svg
+///
+/// Renderer from ANSI styled text to a terminal window SVG made of monospaced
+/// `<text>` and `<tspan>` runs.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Svg {
+    font_family: String,
+    font_size: f64,
+    line_height: f64,
+    palette: Palette,
+    chrome: Chrome,
+    title: Option<String>,
+    padding: f64,
+    columns: Option<usize>,
+}
+
+impl Svg {
+    /// Create a new Svg renderer with a window chrome and the default palette.
+    pub fn new() -> Self {
+        Self {
+            font_family: DEFAULT_FONT_FAMILY.to_string(),
+            font_size: 14.0,
+            line_height: 1.4,
+            palette: Palette::default(),
+            chrome: Chrome::Window,
+            title: None,
+            padding: 16.0,
+            columns: None,
+        }
+    }
+
+    /// Set the CSS font family. It should name monospaced fonts.
+    pub fn font_family(mut self, font_family: &str) -> Self {
+        self.font_family = font_family.to_string();
+        self
+    }
+
+    /// Set the font size in pixels.
+    pub fn font_size(mut self, font_size: f64) -> Self {
+        self.font_size = font_size;
+        self
+    }
+
+    /// Set the line height as a multiple of the font size.
+    pub fn line_height(mut self, line_height: f64) -> Self {
+        self.line_height = line_height;
+        self
+    }
+
+    /// Set the palette used for the ANSI colours and the window background.
+    pub fn palette(mut self, palette: Palette) -> Self {
+        self.palette = palette;
+        self
+    }
+
+    /// Set the window decorations.
+    pub fn chrome(mut self, chrome: Chrome) -> Self {
+        self.chrome = chrome;
+        self
+    }
+
+    /// Set the title shown in the window chrome.
+    pub fn title(mut self, title: &str) -> Self {
+        self.title = Some(title.to_string());
+        self
+    }
+
+    /// Set the padding around the terminal content in pixels.
+    pub fn padding(mut self, padding: f64) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    /// Set a fixed terminal width in columns. By default the widest line is used.
+    pub fn columns(mut self, columns: usize) -> Self {
+        self.columns = Some(columns);
+        self
+    }
+
+    /// Render text containing ANSI escape sequences.
+    pub fn render(&self, text: &str) -> String {
+        self.render_styled(&parse_ansi(text))
+    }
+
+    /// Render a `StyledString`.
+    pub fn render_styled(&self, styled: &StyledString) -> String {
+        let mut lines = styled.lines();
+        if lines.len() > 1 && lines.last().is_some_and(StyledString::is_empty) {
+            lines.pop();
+        }
+
+        let cell_width = self.font_size * 0.6;
+        let line_height = self.font_size * self.line_height;
+        let columns = self
+            .columns
+            .unwrap_or_else(|| lines.iter().map(StyledString::width).max().unwrap_or(0));
+        let top = match self.chrome {
+            Chrome::None => 0.0,
+            Chrome::Window => CHROME_HEIGHT,
+        };
+        let width = columns as f64 * cell_width + self.padding * 2.0;
+        let height = top + lines.len() as f64 * line_height + self.padding * 2.0;
+
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\">\n",
+            w = num(width),
+            h = num(height)
+        );
+        svg.push_str(&format!(
+            "<rect width=\"{}\" height=\"{}\" rx=\"6\" fill=\"{}\"/>\n",
+            num(width),
+            num(height),
+            self.palette.background.to_hex()
+        ));
+
+        if self.chrome == Chrome::Window {
+            for (i, color) in BUTTON_COLORS.iter().enumerate() {
+                svg.push_str(&format!(
+                    "<circle cx=\"{}\" cy=\"{}\" r=\"6\" fill=\"{color}\"/>\n",
+                    num(self.padding + 6.0 + i as f64 * 20.0),
+                    num(CHROME_HEIGHT / 2.0)
+                ));
+            }
+            if let Some(title) = &self.title {
+                svg.push_str(&format!(
+                    "<text x=\"{}\" y=\"{}\" text-anchor=\"middle\" font-family=\"{}\" font-size=\"{}\" fill=\"{}\" opacity=\"0.7\">{}</text>\n",
+                    num(width / 2.0),
+                    num(CHROME_HEIGHT / 2.0 + self.font_size * 0.35),
+                    escape(&self.font_family),
+                    num(self.font_size),
+                    self.palette.foreground.to_hex(),
+                    escape(title)
+                ));
+            }
+        }
+
+        svg.push_str(&format!(
+            "<g font-family=\"{}\" font-size=\"{}\" fill=\"{}\">\n",
+            escape(&self.font_family),
+            num(self.font_size),
+            self.palette.foreground.to_hex()
+        ));
+        for (row, line) in lines.iter().enumerate() {
+            let line_top = top + self.padding + row as f64 * line_height;
+            let baseline = line_top + (line_height + self.font_size) / 2.0 - self.font_size * 0.15;
+
+            let mut column = 0;
+            let mut backgrounds = String::new();
+            let mut runs = String::new();
+            for span in line.spans() {
+                let span_width: usize = span.text.chars().map(char_width).sum();
+                let x = self.padding + column as f64 * cell_width;
+                let (fg, bg) = self.colors(&span.style);
+                if let Some(bg) = bg {
+                    backgrounds.push_str(&format!(
+                        "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\"/>\n",
+                        num(x),
+                        num(line_top),
+                        num(span_width as f64 * cell_width),
+                        num(line_height),
+                        bg.to_hex()
+                    ));
+                }
+                runs.push_str(&format!("<tspan x=\"{}\"", num(x)));
+                if let Some(fg) = fg {
+                    runs.push_str(&format!(" fill=\"{}\"", fg.to_hex()));
+                }
+                runs.push_str(&decoration_attributes(&span.style));
+                runs.push('>');
+                runs.push_str(&escape(&span.text));
+                runs.push_str("</tspan>");
+                column += span_width;
+            }
+
+            svg.push_str(&backgrounds);
+            if !runs.is_empty() {
+                svg.push_str(&format!(
+                    "<text y=\"{}\" xml:space=\"preserve\">{runs}</text>\n",
+                    num(baseline)
+                ));
+            }
+        }
+        svg.push_str("</g>\n</svg>\n");
+        svg
+    }
+
+    /// Work out the foreground and background of a span, swapping them for inverted text.
+    fn colors(&self, style: &Style) -> (Option<RGB>, Option<RGB>) {
+        let fg = style.fg.map(|color| self.palette.resolve(color));
+        let bg = style.bg.map(|color| self.palette.resolve(color));
+        if style.has_decoration(Decoration::Invert) {
+            (
+                Some(bg.unwrap_or(self.palette.background)),
+                Some(fg.unwrap_or(self.palette.foreground)),
+            )
+        } else {
+            (fg, bg)
+        }
+    }
+}
+
+impl Default for Svg {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn decoration_attributes(style: &Style) -> String {
+    let mut attributes = String::new();
+    for decoration in style.decorations() {
+        match decoration {
+            Decoration::Bold => attributes.push_str(" font-weight=\"bold\""),
+            Decoration::Dim => attributes.push_str(" opacity=\"0.7\""),
+            Decoration::Italic => attributes.push_str(" font-style=\"italic\""),
+            Decoration::Underline => attributes.push_str(" text-decoration=\"underline\""),
+            Decoration::Invert => {}
+        }
+    }
+    attributes
+}
+
+/// Escape text for XML. Control characters other than tabs, such as the bells and backspaces
+/// terminal captures often contain, aren't allowed in XML 1.0 and are dropped.
+fn escape(text: &str) -> String {
+    html::escape(&text.replace(|c: char| c < ' ' && c != '\t', ""))
+}
+
+/// Format a number with at most two decimals and no trailing zeros, so output is stable.
+fn num(value: f64) -> String {
+    let formatted = format!("{value:.2}");
+    formatted
+        .trim_end_matches('0')
+        .trim_end_matches('.')
+        .to_string()
+}
+
+/// Render text containing ANSI escape sequences using the default options.
+pub fn to_svg(text: &str) -> String {
+    Svg::new().render(text)
+}
@@ -0,0 +1,361 @@
+//! Render rows and columns of styled cells.
+//!
+//! ```rust
+//! use rustyhues::table::{Align, Table};
+//! use rustyhues::{BorderStyle, StyledString, Stylize};
+//!
+//! let table = Table::new()
+//!     .header(["Crate", "Downloads"])
+//!     .row([StyledString::from("rustyhues".green()), "1,024".into()])
+//!     .align(1, Align::Right)
+//!     .border(BorderStyle::Ascii);
+//! println!("{table}");
+//! ```
+use crate::{
+    border::BorderStyle,
+    style::Style,
+    styled::StyledString,
+    truncate::{truncate, TruncateOptions},
+    wrap::{wrap, WrapOptions},
+};
+use core::fmt;
+
+/// Align
+///
+/// Horizontal alignment of cell content.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Align {
+    #[default]
+    Left,
+    Right,
+    Center,
+}
+
+/// Overflow
+///
+/// What happens to cell content that is wider than its column's maximum width.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Overflow {
+    /// Wrap onto more lines on word boundaries.
+    #[default]
+    Wrap,
+    /// Cut off with an ellipsis.
+    Truncate,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+struct ColumnOptions {
+    align: Align,
+    max_width: Option<usize>,
+    overflow: Overflow,
+}
+
+/// Table
+///
+/// Rows and columns of styled cells, measured by visible width. Displaying a Table follows
+/// the same terminal detection rules as `Paint`, so it falls back to plain text when colour
+/// is disabled.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Table {
+    header: Option<Vec<StyledString>>,
+    rows: Vec<Vec<StyledString>>,
+    columns: Vec<ColumnOptions>,
+    border: BorderStyle,
+    border_style: Style,
+    header_style: Style,
+    zebra: Option<Style>,
+    padding: usize,
+}
+
+impl Table {
+    /// Create a new, empty Table with light borders.
+    pub fn new() -> Self {
+        Self {
+            header: None,
+            rows: Vec::new(),
+            columns: Vec::new(),
+            border: BorderStyle::Light,
+            border_style: Style::new(),
+            header_style: Style::new(),
+            zebra: None,
+            padding: 1,
+        }
+    }
+
+    /// Set the header row.
+    pub fn header<I, C>(mut self, cells: I) -> Self
+    where
+        I: IntoIterator<Item = C>,
+        C: Into<StyledString>,
+    {
+        self.header = Some(cells.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Add a row. Cells can be anything that converts into a `StyledString`, such as `&str`,
+    /// `String` or `Paint`.
+    pub fn row<I, C>(mut self, cells: I) -> Self
+    where
+        I: IntoIterator<Item = C>,
+        C: Into<StyledString>,
+    {
+        self.rows.push(cells.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Add a row to an existing Table.
+    pub fn push_row<I, C>(&mut self, cells: I)
+    where
+        I: IntoIterator<Item = C>,
+        C: Into<StyledString>,
+    {
+        self.rows.push(cells.into_iter().map(Into::into).collect());
+    }
+
+    fn column_mut(&mut self, column: usize) -> &mut ColumnOptions {
+        if self.columns.len() <= column {
+            self.columns.resize(column + 1, ColumnOptions::default());
+        }
+        &mut self.columns[column]
+    }
+
+    /// Set the alignment of a column.
+    pub fn align(mut self, column: usize, align: Align) -> Self {
+        self.column_mut(column).align = align;
+        self
+    }
+
+    /// Limit the width of a column, wrapping or truncating content that is wider.
+    pub fn max_width(mut self, column: usize, width: usize, overflow: Overflow) -> Self {
+        let options = self.column_mut(column);
+        options.max_width = Some(width.max(1));
+        options.overflow = overflow;
+        self
+    }
+
+    /// Set the characters used for borders.
+    pub fn border(mut self, border: BorderStyle) -> Self {
+        self.border = border;
+        self
+    }
+
+    /// Set the style of the borders.
+    pub fn border_style(mut self, style: Style) -> Self {
+        self.border_style = style;
+        self
+    }
+
+    /// Set the style of the header row. Cell styles take priority over it.
+    pub fn header_style(mut self, style: Style) -> Self {
+        self.header_style = style;
+        self
+    }
+
+    /// Set a style applied to every other row, starting with the second, to make long tables
+    /// easier to follow. Cell styles take priority over it.
+    pub fn zebra(mut self, style: Style) -> Self {
+        self.zebra = Some(style);
+        self
+    }
+
+    /// Set the number of spaces between cell content and borders. Defaults to 1.
+    pub fn padding(mut self, padding: usize) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    /// Render the Table into styled lines joined with `\n`.
+    pub fn render(&self) -> StyledString {
+        let column_count = self
+            .header
+            .iter()
+            .chain(&self.rows)
+            .map(Vec::len)
+            .max()
+            .unwrap_or(0);
+        if column_count == 0 {
+            return StyledString::new();
+        }
+        let options: Vec<ColumnOptions> = (0..column_count)
+            .map(|i| self.columns.get(i).copied().unwrap_or_default())
+            .collect();
+
+        let layout = |row: &[StyledString]| -> Vec<Vec<StyledString>> {
+            (0..column_count)
+                .map(|i| {
+                    let cell = row.get(i).cloned().unwrap_or_default();
+                    cell_lines(&cell, &options[i])
+                })
+                .collect()
+        };
+        let header = self.header.as_deref().map(layout);
+        let rows: Vec<Vec<Vec<StyledString>>> = self.rows.iter().map(|row| layout(row)).collect();
+
+        let widths: Vec<usize> = (0..column_count)
+            .map(|i| {
+                header
+                    .iter()
+                    .chain(&rows)
+                    .flat_map(|row| row[i].iter().map(StyledString::width))
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+
+        let mut lines = Vec::new();
+        let chars = self.border.chars();
+        if let Some(chars) = chars {
+            lines.push(self.rule(
+                &widths,
+                chars.horizontal,
+                chars.top_left,
+                chars.top_junction,
+                chars.top_right,
+            ));
+        }
+        if let Some(header) = &header {
+            self.push_row_lines(&mut lines, header, &widths, &options, self.header_style);
+            if let Some(chars) = chars {
+                lines.push(self.rule(
+                    &widths,
+                    chars.horizontal,
+                    chars.left_junction,
+                    chars.cross,
+                    chars.right_junction,
+                ));
+            }
+        }
+        for (i, row) in rows.iter().enumerate() {
+            let base = match self.zebra {
+                Some(zebra) if i % 2 == 1 => zebra,
+                _ => Style::new(),
+            };
+            self.push_row_lines(&mut lines, row, &widths, &options, base);
+        }
+        if let Some(chars) = chars {
+            lines.push(self.rule(
+                &widths,
+                chars.horizontal,
+                chars.bottom_left,
+                chars.bottom_junction,
+                chars.bottom_right,
+            ));
+        }
+
+        let mut rendered = StyledString::new();
+        for (i, line) in lines.into_iter().enumerate() {
+            if i > 0 {
+                rendered.push_str("\n");
+            }
+            rendered.append(line);
+        }
+        rendered
+    }
+
+    fn rule(
+        &self,
+        widths: &[usize],
+        horizontal: char,
+        left: char,
+        junction: char,
+        right: char,
+    ) -> StyledString {
+        let mut rule = String::new();
+        rule.push(left);
+        for (i, width) in widths.iter().enumerate() {
+            if i > 0 {
+                rule.push(junction);
+            }
+            rule.extend(std::iter::repeat_n(horizontal, width + self.padding * 2));
+        }
+        rule.push(right);
+        StyledString::styled(self.border_style, rule)
+    }
+
+    fn push_row_lines(
+        &self,
+        lines: &mut Vec<StyledString>,
+        row: &[Vec<StyledString>],
+        widths: &[usize],
+        options: &[ColumnOptions],
+        base: Style,
+    ) {
+        let height = row.iter().map(Vec::len).max().unwrap_or(1).max(1);
+        let vertical = self.border.chars().map(|chars| chars.vertical);
+        let last = widths.len() - 1;
+
+        for line_index in 0..height {
+            let mut line = StyledString::new();
+            if let Some(vertical) = vertical {
+                line.push(self.border_style, vertical.to_string());
+            }
+            for (i, width) in widths.iter().enumerate() {
+                if let (true, Some(vertical)) = (i > 0, vertical) {
+                    line.push(self.border_style, vertical.to_string());
+                }
+                let content = row[i].get(line_index).cloned().unwrap_or_default();
+                let left_padding = if vertical.is_none() && i == 0 {
+                    0
+                } else {
+                    self.padding
+                };
+                let right_padding = if vertical.is_none() && i == last {
+                    0
+                } else {
+                    self.padding
+                };
+                let cell = align(
+                    content,
+                    *width,
+                    options[i].align,
+                    left_padding,
+                    right_padding,
+                );
+                line.append(cell.with_base_style(base));
+            }
+            if let Some(vertical) = vertical {
+                line.push(self.border_style, vertical.to_string());
+            }
+            lines.push(line);
+        }
+    }
+}
+
+impl fmt::Display for Table {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.render().fmt(f)
+    }
+}
+
+/// Split a cell into lines, applying its column's width limit.
+fn cell_lines(cell: &StyledString, options: &ColumnOptions) -> Vec<StyledString> {
+    match (options.max_width, options.overflow) {
+        (None, _) => cell.lines(),
+        (Some(width), Overflow::Wrap) => wrap(cell, &WrapOptions::new(width)),
+        (Some(width), Overflow::Truncate) => cell
+            .lines()
+            .iter()
+            .map(|line| truncate(line, &TruncateOptions::new(width)))
+            .collect(),
+    }
+}
+
+fn align(
+    content: StyledString,
+    width: usize,
+    align: Align,
+    left_padding: usize,
+    right_padding: usize,
+) -> StyledString {
+    let extra = width.saturating_sub(content.width());
+    let (left, right) = match align {
+        Align::Left => (0, extra),
+        Align::Right => (extra, 0),
+        Align::Center => (extra / 2, extra - extra / 2),
+    };
+    let mut cell = StyledString::new();
+    cell.push_str(&" ".repeat(left + left_padding));
+    cell.append(content);
+    cell.push_str(&" ".repeat(right + right_padding));
+    cell
+}
@@ -0,0 +1,403 @@
+//! Read compiled terminfo entries to find out what a terminal supports.
+//!
+//! Only available with the `terminfo` feature. When enabled, `env::detect_color_level()` also
+//! checks the terminfo entry for `TERM`.
+//!
+//! ```rust,no_run
+//! use rustyhues::terminfo::TermInfo;
+//!
+//! let info = TermInfo::load("xterm-256color").unwrap();
+//! println!("{:?} colours, level {:?}", info.colors(), info.color_level());
+//! ```
+use crate::env::ColorLevel;
+use core::fmt;
+use std::{
+    collections::HashMap,
+    env, fs, io,
+    path::{Path, PathBuf},
+    sync::RwLock,
+};
+
+/// The magic number of entries with 16-bit numbers.
+const LEGACY_MAGIC: u16 = 0o432;
+/// The magic number of entries with 32-bit numbers, written by ncurses 6.1 and later.
+const NUMBER32_MAGIC: u16 = 0o1036;
+
+/// Standard boolean capabilities, by their index in a compiled entry.
+const BOOLEAN_NAMES: [(usize, &str); 3] = [(0, "bw"), (1, "am"), (28, "bce")];
+
+/// Standard numeric capabilities, by their index in a compiled entry.
+const NUMBER_NAMES: [(usize, &str); 4] = [(0, "cols"), (2, "lines"), (13, "colors"), (14, "pairs")];
+
+/// Standard string capabilities, by their index in a compiled entry.
+const STRING_NAMES: [(usize, &str); 11] = [
+    (27, "bold"),
+    (30, "dim"),
+    (34, "rev"),
+    (36, "smul"),
+    (39, "sgr0"),
+    (297, "op"),
+    (302, "setf"),
+    (303, "setb"),
+    (311, "sitm"),
+    (359, "setaf"),
+    (360, "setab"),
+];
+
+/// Terminfo Error
+///
+/// The reasons a terminfo entry can't be loaded.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TermInfoError {
+    /// No entry for the terminal was found in any of the search directories.
+    NotFound { name: String },
+    /// The entry was found but couldn't be read.
+    Io(io::ErrorKind),
+    /// The file doesn't start with a known terminfo magic number.
+    InvalidMagic(u16),
+    /// The file ended before the end of a section.
+    Truncated,
+    /// A section size or string offset is out of range.
+    InvalidSection,
+}
+
+impl fmt::Display for TermInfoError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TermInfoError::NotFound { name } => write!(f, "no terminfo entry for `{name}`"),
+            TermInfoError::Io(kind) => write!(f, "couldn't read terminfo entry: {kind}"),
+            TermInfoError::InvalidMagic(magic) => {
+                write!(f, "invalid terminfo magic number {magic:#o}")
+            }
+            TermInfoError::Truncated => write!(f, "terminfo entry is truncated"),
+            TermInfoError::InvalidSection => write!(f, "terminfo entry has an invalid section"),
+        }
+    }
+}
+
+impl std::error::Error for TermInfoError {}
+
+/// Term Info
+///
+/// The capabilities of a compiled terminfo entry. Standard capabilities are looked up by their
+/// short names, and only those relevant to styling are kept (see `flag`, `number` and
+/// `string`). Extended capabilities such as `Tc` and `RGB` are kept by the name in the entry.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TermInfo {
+    names: Vec<String>,
+    flags: HashMap<String, bool>,
+    numbers: HashMap<String, i32>,
+    strings: HashMap<String, Vec<u8>>,
+}
+
+impl TermInfo {
+    /// Find and load the entry for a terminal name, searching `search_dirs()` in order.
+    pub fn load(name: &str) -> Result<Self, TermInfoError> {
+        let not_found = || TermInfoError::NotFound {
+            name: name.to_string(),
+        };
+        let first = name.bytes().next().ok_or_else(not_found)?;
+        if name.contains(['/', '\\']) || name.starts_with('.') {
+            return Err(not_found());
+        }
+
+        for dir in search_dirs() {
+            // Entries are stored under their first character, or its hex code on file systems
+            // that are not case sensitive.
+            for subdir in [(first as char).to_string(), format!("{first:02x}")] {
+                let path = dir.join(subdir).join(name);
+                match fs::read(&path) {
+                    Ok(bytes) => return Self::parse(&bytes),
+                    Err(error) if error.kind() == io::ErrorKind::NotFound => {}
+                    Err(error) => return Err(TermInfoError::Io(error.kind())),
+                }
+            }
+        }
+        Err(not_found())
+    }
+
+    /// Load the entry for the terminal in `TERM`.
+    pub fn from_env() -> Result<Self, TermInfoError> {
+        let term = env::var("TERM").unwrap_or_default();
+        Self::load(&term)
+    }
+
+    /// Load an entry from a file.
+    pub fn load_path(path: impl AsRef<Path>) -> Result<Self, TermInfoError> {
+        let bytes = fs::read(path).map_err(|error| TermInfoError::Io(error.kind()))?;
+        Self::parse(&bytes)
+    }
+
+    /// Parse a compiled entry, in either the legacy format or the format with 32-bit numbers,
+    /// including any extended capabilities.
+    pub fn parse(bytes: &[u8]) -> Result<Self, TermInfoError> {
+        let mut reader = Reader { bytes, position: 0 };
+        let magic = reader.u16()?;
+        let number_size = match magic {
+            LEGACY_MAGIC => 2,
+            NUMBER32_MAGIC => 4,
+            _ => return Err(TermInfoError::InvalidMagic(magic)),
+        };
+        let names_size = reader.count()?;
+        let bool_count = reader.count()?;
+        let number_count = reader.count()?;
+        let string_count = reader.count()?;
+        let table_size = reader.count()?;
+
+        let mut info = TermInfo::default();
+        let names = reader.take(names_size)?;
+        let names = names.split(|byte| *byte == 0).next().unwrap_or_default();
+        info.names = String::from_utf8_lossy(names)
+            .split('|')
+            .map(str::to_string)
+            .collect();
+
+        let flags = reader.take(bool_count)?.to_vec();
+        reader.align();
+        let numbers = reader.numbers(number_count, number_size)?;
+        let offsets = reader.offsets(string_count)?;
+        let table = reader.take(table_size)?;
+
+        for (index, name) in BOOLEAN_NAMES {
+            if flags.get(index) == Some(&1) {
+                info.flags.insert(name.to_string(), true);
+            }
+        }
+        for (index, name) in NUMBER_NAMES {
+            if let Some(value) = numbers.get(index).copied().filter(|value| *value >= 0) {
+                info.numbers.insert(name.to_string(), value);
+            }
+        }
+        for (index, name) in STRING_NAMES {
+            if let Some(value) = offsets
+                .get(index)
+                .and_then(|offset| string_at(table, *offset))
+            {
+                info.strings.insert(name.to_string(), value.to_vec());
+            }
+        }
+
+        reader.align();
+        if reader.position < bytes.len() {
+            info.parse_extended(&mut reader, number_size)?;
+        }
+        Ok(info)
+    }
+
+    /// Parse the extended capabilities that follow the standard ones.
+    fn parse_extended(
+        &mut self,
+        reader: &mut Reader<'_>,
+        number_size: usize,
+    ) -> Result<(), TermInfoError> {
+        let bool_count = reader.count()?;
+        let number_count = reader.count()?;
+        let string_count = reader.count()?;
+        let _item_count = reader.count()?;
+        let table_size = reader.count()?;
+
+        let flags = reader.take(bool_count)?.to_vec();
+        reader.align();
+        let numbers = reader.numbers(number_count, number_size)?;
+        let value_offsets = reader.offsets(string_count)?;
+        let name_offsets = reader.offsets(bool_count + number_count + string_count)?;
+        let table = reader.take(table_size)?;
+
+        // The names follow the string values in the table, and their offsets start from there.
+        let values_size = value_offsets
+            .iter()
+            .filter_map(|offset| Some(*offset as usize + string_at(table, *offset)?.len() + 1))
+            .max()
+            .unwrap_or(0);
+        let names_table = table
+            .get(values_size..)
+            .ok_or(TermInfoError::InvalidSection)?;
+        let mut names = name_offsets.iter().map(|offset| {
+            string_at(names_table, *offset)
+                .map(|name| String::from_utf8_lossy(name).into_owned())
+                .ok_or(TermInfoError::InvalidSection)
+        });
+
+        for flag in flags {
+            let name = names.next().ok_or(TermInfoError::InvalidSection)??;
+            if flag == 1 {
+                self.flags.insert(name, true);
+            }
+        }
+        for value in numbers {
+            let name = names.next().ok_or(TermInfoError::InvalidSection)??;
+            if value >= 0 {
+                self.numbers.insert(name, value);
+            }
+        }
+        for offset in value_offsets {
+            let name = names.next().ok_or(TermInfoError::InvalidSection)??;
+            if let Some(value) = string_at(table, offset) {
+                self.strings.insert(name, value.to_vec());
+            }
+        }
+        Ok(())
+    }
+
+    /// Get the names of the terminal, the first being the one used to look it up and the last
+    /// usually a description.
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+
+    /// Check whether a boolean capability such as `am`, `bce`, `Tc` or `RGB` is set.
+    pub fn flag(&self, name: &str) -> bool {
+        self.flags.get(name).copied().unwrap_or(false)
+    }
+
+    /// Get a numeric capability such as `colors` or `pairs`.
+    pub fn number(&self, name: &str) -> Option<i32> {
+        self.numbers.get(name).copied()
+    }
+
+    /// Get a string capability such as `setaf` or `sgr0`, without expanding its parameters.
+    pub fn string(&self, name: &str) -> Option<&[u8]> {
+        self.strings.get(name).map(Vec::as_slice)
+    }
+
+    /// Get the number of colours the terminal supports.
+    pub fn colors(&self) -> Option<i32> {
+        self.number("colors")
+    }
+
+    /// Check whether the terminal supports RGB colours, through the `Tc` or `RGB` extensions or
+    /// a direct colour `colors` count.
+    pub fn true_color(&self) -> bool {
+        self.flag("Tc")
+            || self.flag("RGB")
+            || self.number("RGB").is_some()
+            || self.string("RGB").is_some()
+            || self.colors().is_some_and(|colors| colors >= 1 << 24)
+    }
+
+    /// Work out the colour level the entry describes. Colours need a `setaf` or `setf`
+    /// capability to be used at all.
+    pub fn color_level(&self) -> ColorLevel {
+        let can_set_color = self.string("setaf").is_some() || self.string("setf").is_some();
+        if !can_set_color {
+            return ColorLevel::None;
+        }
+        if self.true_color() {
+            return ColorLevel::TrueColor;
+        }
+        match self.colors().unwrap_or(0) {
+            256.. => ColorLevel::Ansi256,
+            8.. => ColorLevel::Basic16,
+            _ => ColorLevel::None,
+        }
+    }
+}
+
+/// Get the directories searched for terminfo entries, in order: `$TERMINFO`, `~/.terminfo`,
+/// each entry of `$TERMINFO_DIRS` (an empty entry means the system directory), and then
+/// `/etc/terminfo`, `/lib/terminfo` and `/usr/share/terminfo`.
+pub fn search_dirs() -> Vec<PathBuf> {
+    const SYSTEM_DIRS: [&str; 3] = ["/etc/terminfo", "/lib/terminfo", "/usr/share/terminfo"];
+
+    let mut dirs = Vec::new();
+    if let Some(dir) = env::var_os("TERMINFO") {
+        dirs.push(PathBuf::from(dir));
+    }
+    if let Some(home) = env::var_os("HOME") {
+        dirs.push(PathBuf::from(home).join(".terminfo"));
+    }
+    if let Ok(list) = env::var("TERMINFO_DIRS") {
+        for dir in list.split(':') {
+            if dir.is_empty() {
+                dirs.push(PathBuf::from("/usr/share/terminfo"));
+            } else {
+                dirs.push(PathBuf::from(dir));
+            }
+        }
+    }
+    for dir in SYSTEM_DIRS {
+        let dir = PathBuf::from(dir);
+        if !dirs.contains(&dir) {
+            dirs.push(dir);
+        }
+    }
+    dirs
+}
+
+/// The last terminal looked up by `cached_color_level`, and its colour level.
+static CACHED_LEVEL: RwLock<Option<(String, Option<ColorLevel>)>> = RwLock::new(None);
+
+/// Get the colour level from the terminfo entry of a terminal, or `None` if it has none.
+/// Lookups are cached, since detection runs every time a `Paint` is displayed.
+pub(crate) fn cached_color_level(term: &str) -> Option<ColorLevel> {
+    if let Some((cached_term, level)) = CACHED_LEVEL.read().unwrap().as_ref() {
+        if cached_term == term {
+            return *level;
+        }
+    }
+    let level = TermInfo::load(term).ok().map(|info| info.color_level());
+    *CACHED_LEVEL.write().unwrap() = Some((term.to_string(), level));
+    level
+}
+
+/// Get the NUL terminated string at an offset into a string table. Negative offsets mean the
+/// capability is absent or cancelled.
+fn string_at(table: &[u8], offset: i16) -> Option<&[u8]> {
+    let rest = table.get(usize::try_from(offset).ok()?..)?;
+    let end = rest.iter().position(|byte| *byte == 0)?;
+    Some(&rest[..end])
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], TermInfoError> {
+        let end = self.position + len;
+        let bytes = self
+            .bytes
+            .get(self.position..end)
+            .ok_or(TermInfoError::Truncated)?;
+        self.position = end;
+        Ok(bytes)
+    }
+
+    fn u16(&mut self) -> Result<u16, TermInfoError> {
+        let bytes = self.take(2)?;
+        Ok(u16::from_le_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn i16(&mut self) -> Result<i16, TermInfoError> {
+        Ok(self.u16()? as i16)
+    }
+
+    /// Read a section size or count, which can't be negative.
+    fn count(&mut self) -> Result<usize, TermInfoError> {
+        usize::try_from(self.i16()?).map_err(|_| TermInfoError::InvalidSection)
+    }
+
+    fn numbers(&mut self, count: usize, size: usize) -> Result<Vec<i32>, TermInfoError> {
+        let bytes = self.take(count * size)?;
+        Ok(bytes
+            .chunks_exact(size)
+            .map(|chunk| match chunk {
+                [a, b] => i16::from_le_bytes([*a, *b]) as i32,
+                [a, b, c, d] => i32::from_le_bytes([*a, *b, *c, *d]),
+                _ => unreachable!("numbers are 2 or 4 bytes"),
+            })
+            .collect())
+    }
+
+    fn offsets(&mut self, count: usize) -> Result<Vec<i16>, TermInfoError> {
+        (0..count).map(|_| self.i16()).collect()
+    }
+
+    /// Skip the padding byte that keeps sections on an even offset.
+    fn align(&mut self) {
+        if self.position % 2 == 1 && self.position < self.bytes.len() {
+            self.position += 1;
+        }
+    }
+}
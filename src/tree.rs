@@ -0,0 +1,204 @@
+//! Render hierarchical output such as dependency graphs and directory listings.
+//!
+//! ```rust
+//! use rustyhues::tree::{Node, Tree};
+//! use rustyhues::{StyledString, Stylize};
+//!
+//! let root = Node::new(StyledString::from("my-app".bold()))
+//!     .child(Node::new("serde").child(Node::new("serde_derive")))
+//!     .child(Node::new(StyledString::from("rustyhues".green())));
+//! println!("{}", Tree::new(root));
+//! ```
+use crate::{
+    env::unicode_supported,
+    style::{Decoration, Style},
+    styled::StyledString,
+};
+use core::fmt;
+
+/// Node
+///
+/// A label and its children. Labels keep their own styles, for example from `Paint`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Node {
+    label: StyledString,
+    children: Vec<Node>,
+    collapsed: bool,
+}
+
+impl Node {
+    /// Create a new Node without children.
+    pub fn new(label: impl Into<StyledString>) -> Self {
+        Self {
+            label: label.into(),
+            children: Vec::new(),
+            collapsed: false,
+        }
+    }
+
+    /// Add a child.
+    pub fn child(mut self, child: Node) -> Self {
+        self.children.push(child);
+        self
+    }
+
+    /// Add a child to an existing Node.
+    pub fn push(&mut self, child: Node) {
+        self.children.push(child);
+    }
+
+    /// Hide the children of this Node, showing how many there are instead.
+    pub fn collapsed(mut self, collapsed: bool) -> Self {
+        self.collapsed = collapsed;
+        self
+    }
+
+    /// Get the children of this Node.
+    pub fn children(&self) -> &[Node] {
+        &self.children
+    }
+}
+
+struct Guides {
+    branch: &'static str,
+    last: &'static str,
+    pipe: &'static str,
+    blank: &'static str,
+}
+
+const UNICODE_GUIDES: Guides = Guides {
+    branch: "├── ",
+    last: "└── ",
+    pipe: "│   ",
+    blank: "    ",
+};
+
+const ASCII_GUIDES: Guides = Guides {
+    branch: "|-- ",
+    last: "`-- ",
+    pipe: "|   ",
+    blank: "    ",
+};
+
+/// Tree
+///
+/// Renders a root `Node` and its descendants with guide lines, which are dimmed by default.
+/// Displaying a Tree follows the same terminal detection rules as `Paint`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Tree {
+    root: Node,
+    guide_style: Style,
+    max_depth: Option<usize>,
+    ascii: Option<bool>,
+}
+
+impl Tree {
+    /// Create a new Tree from its root Node.
+    pub fn new(root: Node) -> Self {
+        Self {
+            root,
+            guide_style: Style::new().decoration(Decoration::Dim),
+            max_depth: None,
+            ascii: None,
+        }
+    }
+
+    /// Set the style of the guide lines.
+    pub fn guide_style(mut self, style: Style) -> Self {
+        self.guide_style = style;
+        self
+    }
+
+    /// Hide Nodes deeper than `depth`, where the root is at depth 0. Their parents show how many
+    /// children were hidden.
+    pub fn max_depth(mut self, depth: usize) -> Self {
+        self.max_depth = Some(depth);
+        self
+    }
+
+    /// Force ASCII (`true`) or box drawing (`false`) guide lines. By default ASCII is used when
+    /// `env::unicode_supported()` is `false`.
+    pub fn ascii(mut self, ascii: bool) -> Self {
+        self.ascii = Some(ascii);
+        self
+    }
+
+    /// Render the Tree into styled lines joined with `\n`.
+    pub fn render(&self) -> StyledString {
+        let guides = if self.ascii.unwrap_or(!unicode_supported()) {
+            &ASCII_GUIDES
+        } else {
+            &UNICODE_GUIDES
+        };
+        let mut lines = Vec::new();
+        self.render_node(&self.root, guides, 0, "", "", &mut lines);
+
+        let mut rendered = StyledString::new();
+        for (i, line) in lines.into_iter().enumerate() {
+            if i > 0 {
+                rendered.push_str("\n");
+            }
+            rendered.append(line);
+        }
+        rendered
+    }
+
+    /// Render a Node. `first` prefixes the first line of its label and `rest` every other line,
+    /// including those of its descendants. Later label lines of a Node with children also get
+    /// the guide leading down to them.
+    fn render_node(
+        &self,
+        node: &Node,
+        guides: &Guides,
+        depth: usize,
+        first: &str,
+        rest: &str,
+        lines: &mut Vec<StyledString>,
+    ) {
+        let collapsed = !node.children.is_empty()
+            && (node.collapsed || self.max_depth.is_some_and(|max| depth >= max));
+
+        // Later lines of the label carry on the guide its children start from.
+        let continuation = if collapsed || node.children.is_empty() {
+            rest.to_string()
+        } else {
+            format!("{rest}{}", guides.pipe)
+        };
+        let label_lines = node.label.lines();
+        let count = label_lines.len();
+        for (i, label) in label_lines.into_iter().enumerate() {
+            let prefix = if i == 0 { first } else { &continuation };
+            let mut line = StyledString::styled(self.guide_style, prefix);
+            line.append(label);
+            if collapsed && i == count - 1 {
+                line.push(self.guide_style, format!(" (+{})", node.children.len()));
+            }
+            lines.push(line);
+        }
+        if collapsed {
+            return;
+        }
+
+        for (i, child) in node.children.iter().enumerate() {
+            let (branch, continuation) = if i == node.children.len() - 1 {
+                (guides.last, guides.blank)
+            } else {
+                (guides.branch, guides.pipe)
+            };
+            self.render_node(
+                child,
+                guides,
+                depth + 1,
+                &format!("{rest}{branch}"),
+                &format!("{rest}{continuation}"),
+                lines,
+            );
+        }
+    }
+}
+
+impl fmt::Display for Tree {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.render().fmt(f)
+    }
+}
@@ -0,0 +1,148 @@
+use crate::{
+    ansi::{char_width, parse_ansi},
+    style::Style,
+    styled::StyledString,
+};
+
+/// Truncate Position
+///
+/// Where text is removed when it is too wide.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TruncatePosition {
+    /// Keep the end of the text, for example `…/src/main.rs`.
+    Start,
+    /// Keep both ends of the text, for example `/home/…/main.rs`.
+    Middle,
+    /// Keep the start of the text, for example `/home/user/…`.
+    #[default]
+    End,
+}
+
+/// Truncate Options
+///
+/// Settings for truncating styled text: the maximum width, where text is removed and the
+/// ellipsis shown in its place.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TruncateOptions {
+    width: usize,
+    position: TruncatePosition,
+    ellipsis: StyledString,
+}
+
+impl TruncateOptions {
+    /// Create new TruncateOptions for truncating the end of text to `width` columns using `…`.
+    pub fn new(width: usize) -> Self {
+        Self {
+            width,
+            position: TruncatePosition::End,
+            ellipsis: StyledString::from("…"),
+        }
+    }
+
+    /// Set where text is removed.
+    pub fn position(mut self, position: TruncatePosition) -> Self {
+        self.position = position;
+        self
+    }
+
+    /// Set the ellipsis shown in place of removed text. It can be styled or empty.
+    pub fn ellipsis(mut self, ellipsis: impl Into<StyledString>) -> Self {
+        self.ellipsis = ellipsis.into();
+        self
+    }
+}
+
+fn chars(text: &StyledString) -> Vec<(char, Style)> {
+    text.spans()
+        .iter()
+        .flat_map(|span| span.text.chars().map(move |c| (c, span.style)))
+        .collect()
+}
+
+/// Take characters from the start of `chars` until `width` columns are used.
+fn take_start(chars: &[(char, Style)], width: usize) -> usize {
+    let mut used = 0;
+    for (i, (c, _)) in chars.iter().enumerate() {
+        used += char_width(*c);
+        if used > width {
+            return i;
+        }
+    }
+    chars.len()
+}
+
+/// Take characters from the end of `chars` until `width` columns are used, returning the
+/// index the kept characters start at.
+fn take_end(chars: &[(char, Style)], width: usize) -> usize {
+    let mut used = 0;
+    let mut start = chars.len();
+    for (i, (c, _)) in chars.iter().enumerate().rev() {
+        used += char_width(*c);
+        if used > width {
+            break;
+        }
+        start = i;
+    }
+    // Don't keep combining marks whose base character was removed.
+    while start < chars.len() && char_width(chars[start].0) == 0 {
+        start += 1;
+    }
+    start
+}
+
+fn collect(chars: &[(char, Style)]) -> StyledString {
+    let mut styled = StyledString::new();
+    for (c, style) in chars {
+        styled.push(*style, c.to_string());
+    }
+    styled
+}
+
+/// Truncate styled text to a maximum width in terminal columns.
+///
+/// Text that already fits is returned unchanged. Otherwise text is removed at the chosen
+/// position and replaced by the ellipsis, with wide characters never cut in half. The result
+/// is a `StyledString`, so escape sequences always stay balanced when it is rendered.
+pub fn truncate(text: &StyledString, options: &TruncateOptions) -> StyledString {
+    if text.width() <= options.width {
+        return text.clone();
+    }
+
+    let ellipsis_width = options.ellipsis.width();
+    if ellipsis_width > options.width {
+        let ellipsis = chars(&options.ellipsis);
+        return collect(&ellipsis[..take_start(&ellipsis, options.width)]);
+    }
+
+    let available = options.width - ellipsis_width;
+    let chars = chars(text);
+    let mut truncated = StyledString::new();
+    match options.position {
+        TruncatePosition::End => {
+            truncated.append(collect(&chars[..take_start(&chars, available)]));
+            truncated.append(options.ellipsis.clone());
+        }
+        TruncatePosition::Start => {
+            truncated.append(options.ellipsis.clone());
+            truncated.append(collect(&chars[take_end(&chars, available)..]));
+        }
+        TruncatePosition::Middle => {
+            let right = available / 2;
+            let left = available - right;
+            let head = take_start(&chars, left);
+            let tail = take_end(&chars, right).max(head);
+            truncated.append(collect(&chars[..head]));
+            truncated.append(options.ellipsis.clone());
+            truncated.append(collect(&chars[tail..]));
+        }
+    }
+    truncated
+}
+
+/// Truncate text containing ANSI escape sequences to a maximum width in terminal columns.
+///
+/// Styles that were open where text is removed are closed, so the result never leaves the
+/// terminal in a broken state.
+pub fn truncate_ansi(text: &str, options: &TruncateOptions) -> String {
+    truncate(&parse_ansi(text), options).to_ansi()
+}
@@ -1,4 +1,6 @@
 #[cfg(windows)]
+use crate::env::TerminalSize;
+#[cfg(windows)]
 use std::sync::OnceLock;
 #[cfg(windows)]
 use windows_sys::Win32::Foundation::INVALID_HANDLE_VALUE;
@@ -64,3 +66,35 @@ pub fn is_tty_stderr() -> bool {
     }
     stderr_vt_enabled()
 }
+
+#[cfg(windows)]
+fn terminal_size(handle: isize) -> Option<TerminalSize> {
+    if !is_console_handle(handle) {
+        return None;
+    }
+    let mut info: CONSOLE_SCREEN_BUFFER_INFO = unsafe { std::mem::zeroed() };
+    let ok = unsafe { GetConsoleScreenBufferInfo(handle, &mut info) };
+    if ok == 0 {
+        return None;
+    }
+
+    let columns = info.srWindow.Right - info.srWindow.Left + 1;
+    let rows = info.srWindow.Bottom - info.srWindow.Top + 1;
+    if columns <= 0 || rows <= 0 {
+        return None;
+    }
+    Some(TerminalSize {
+        columns: columns as u16,
+        rows: rows as u16,
+    })
+}
+
+#[cfg(windows)]
+pub fn terminal_size_stdout() -> Option<TerminalSize> {
+    terminal_size(std_handle(STD_OUTPUT_HANDLE))
+}
+
+#[cfg(windows)]
+pub fn terminal_size_stderr() -> Option<TerminalSize> {
+    terminal_size(std_handle(STD_ERROR_HANDLE))
+}
@@ -0,0 +1,147 @@
+use crate::{
+    ansi::{char_width, parse_ansi},
+    style::Style,
+    styled::StyledString,
+};
+
+/// Wrap Options
+///
+/// Settings for wrapping styled text: the width to wrap to and the indents placed before the
+/// first line and before every following line.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WrapOptions {
+    width: usize,
+    initial_indent: StyledString,
+    subsequent_indent: StyledString,
+}
+
+impl WrapOptions {
+    /// Create new WrapOptions for wrapping to `width` columns, without any indents.
+    pub fn new(width: usize) -> Self {
+        Self {
+            width,
+            initial_indent: StyledString::new(),
+            subsequent_indent: StyledString::new(),
+        }
+    }
+
+    /// Set the prefix placed before the first line, for example a bullet or a label.
+    pub fn initial_indent(mut self, indent: impl Into<StyledString>) -> Self {
+        self.initial_indent = indent.into();
+        self
+    }
+
+    /// Set the prefix placed before every line after the first, for a hanging indent.
+    pub fn subsequent_indent(mut self, indent: impl Into<StyledString>) -> Self {
+        self.subsequent_indent = indent.into();
+        self
+    }
+}
+
+type Word = Vec<(char, Style)>;
+
+fn word_width(word: &Word) -> usize {
+    word.iter().map(|(c, _)| char_width(*c)).sum()
+}
+
+fn push_word(line: &mut StyledString, word: &Word) {
+    for (c, style) in word {
+        line.push(*style, c.to_string());
+    }
+}
+
+/// Split a line into alternating whitespace and word runs, each run holding its characters.
+fn runs(line: &StyledString) -> Vec<(bool, Word)> {
+    let mut runs: Vec<(bool, Word)> = Vec::new();
+    for span in line.spans() {
+        for c in span.text.chars() {
+            let is_space = c.is_whitespace();
+            match runs.last_mut() {
+                Some((space, run)) if *space == is_space => run.push((c, span.style)),
+                _ => runs.push((is_space, vec![(c, span.style)])),
+            }
+        }
+    }
+    runs
+}
+
+/// Wrap styled text to a width on word boundaries.
+///
+/// Widths are measured in terminal columns, so wide characters count twice. Existing newlines
+/// are kept, whitespace at the point of a break is dropped and words that are too long for a
+/// line on their own are split. Every returned line keeps the styles of its text, so colours
+/// carry on across line breaks.
+pub fn wrap(text: &StyledString, options: &WrapOptions) -> Vec<StyledString> {
+    let mut lines = Vec::new();
+    let mut indent = &options.initial_indent;
+
+    for paragraph in text.lines() {
+        let mut line = indent.clone();
+        let mut line_width = indent.width();
+        let mut has_words = false;
+        let mut pending_space: Word = Vec::new();
+
+        for (is_space, run) in runs(&paragraph) {
+            if is_space {
+                pending_space = run;
+                continue;
+            }
+
+            let width = word_width(&run);
+            let space_width = word_width(&pending_space);
+            if line_width + space_width + width <= options.width {
+                if has_words || !pending_space.is_empty() {
+                    push_word(&mut line, &pending_space);
+                    line_width += space_width;
+                }
+                push_word(&mut line, &run);
+                line_width += width;
+                has_words = true;
+                pending_space.clear();
+                continue;
+            }
+
+            if has_words {
+                lines.push(std::mem::take(&mut line));
+                indent = &options.subsequent_indent;
+                line = indent.clone();
+                line_width = indent.width();
+            }
+            pending_space.clear();
+
+            // Split words that don't fit on an empty line.
+            for (c, style) in run {
+                let width = char_width(c);
+                if line_width + width > options.width && line_width > indent.width() {
+                    lines.push(std::mem::take(&mut line));
+                    indent = &options.subsequent_indent;
+                    line = indent.clone();
+                    line_width = indent.width();
+                }
+                line.push(style, c.to_string());
+                line_width += width;
+            }
+            has_words = true;
+        }
+
+        if !has_words {
+            line = StyledString::new();
+        }
+        lines.push(line);
+        indent = &options.subsequent_indent;
+    }
+
+    lines
+}
+
+/// Wrap text containing ANSI escape sequences to a width on word boundaries.
+///
+/// Lines are joined with `\n`. Styles are closed at the end of every line and reopened at the
+/// start of the next, so each line can be shown on its own.
+pub fn wrap_ansi(text: &str, options: &WrapOptions) -> String {
+    wrap(&parse_ansi(text), options)
+        .iter()
+        .map(StyledString::to_ansi)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
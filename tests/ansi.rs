@@ -0,0 +1,65 @@
+use rustyhues::*;
+use std::borrow::Cow;
+
+#[test]
+fn strip_plain_text_is_borrowed() {
+    assert!(matches!(
+        strip_ansi("plain text"),
+        Cow::Borrowed("plain text")
+    ));
+}
+
+#[test]
+fn strip_sgr_sequences() {
+    assert_eq!(strip_ansi("\x1b[34;1mX\x1b[0m"), "X");
+    assert_eq!(strip_ansi("\x1b[38;2;255;0;128mA\x1b[0mB\x1b[mC"), "ABC");
+}
+
+#[test]
+fn strip_painted_output() {
+    env::set_color_choice(env::ColorChoice::Always);
+    let painted = format!("{}", "Hello".blue().bg_white().bold());
+    assert_eq!(strip_ansi(&painted), "Hello");
+}
+
+#[test]
+fn strip_osc_sequences() {
+    let hyperlink = "\x1b]8;;https://example.com\x1b\\link\x1b]8;;\x1b\\";
+    assert_eq!(strip_ansi(hyperlink), "link");
+    assert_eq!(strip_ansi("\x1b]0;title\x07text"), "text");
+}
+
+#[test]
+fn strip_other_escapes() {
+    assert_eq!(strip_ansi("\x1b(Babc\x1b7def\x1b8"), "abcdef");
+    assert_eq!(strip_ansi("a\x1b[2K\x1b[1Gb"), "ab");
+    assert_eq!(strip_ansi("a\u{9b}31mb"), "ab");
+}
+
+#[test]
+fn strip_unterminated_escape() {
+    assert_eq!(strip_ansi("abc\x1b[31"), "abc");
+    assert_eq!(strip_ansi("abc\x1b"), "abc");
+}
+
+#[test]
+fn width_ignores_escapes() {
+    assert_eq!(visible_width("\x1b[31mred\x1b[0m"), 3);
+    assert_eq!(visible_width(""), 0);
+}
+
+#[test]
+fn width_counts_wide_characters() {
+    assert_eq!(visible_width("日本語"), 6);
+    assert_eq!(visible_width("ｆｕｌｌ"), 8);
+    assert_eq!(visible_width("한국어"), 6);
+    assert_eq!(visible_width("🚀 go"), 5);
+}
+
+#[test]
+fn width_counts_zero_width_characters() {
+    assert_eq!(visible_width("e\u{301}"), 1);
+    assert_eq!(visible_width("a\u{200b}b"), 2);
+    assert_eq!(visible_width("\u{feff}x"), 1);
+    assert_eq!(visible_width("tab\tstop"), 7);
+}
@@ -0,0 +1,114 @@
+use rustyhues::*;
+
+fn red_bold() -> String {
+    format!("\x1b[{};{}m", COLOR_RED_CODE, DECORATION_BOLD_CODE)
+}
+
+#[test]
+fn if_then_else() {
+    env::set_color_choice(env::ColorChoice::Always);
+    let painted = "X"
+        .if_then(true, |s| s.red().bold())
+        .else_then(|s| s.green());
+    assert_eq!(painted.to_string(), format!("{}X\x1b[0m", red_bold()));
+
+    let painted = "X"
+        .if_then(false, |s| s.red().bold())
+        .else_then(|s| s.green());
+    assert_eq!(
+        painted.to_string(),
+        format!("\x1b[{}mX\x1b[0m", COLOR_GREEN_CODE)
+    );
+}
+
+#[test]
+fn else_if_chains() {
+    env::set_color_choice(env::ColorChoice::Always);
+    let paint = |value: i32| {
+        value
+            .if_then(value < 0, |s| s.red())
+            .else_if_then(value == 0, |s| s.dim())
+            .else_if_then(value > 100, |s| s.yellow().underline())
+            .else_then(|s| s.green())
+            .to_string()
+    };
+    assert_eq!(paint(-1), format!("\x1b[{}m-1\x1b[0m", COLOR_RED_CODE));
+    assert_eq!(paint(0), format!("\x1b[{}m0\x1b[0m", DECORATION_DIM_CODE));
+    assert_eq!(
+        paint(101),
+        format!(
+            "\x1b[{};{}m101\x1b[0m",
+            COLOR_YELLOW_CODE, DECORATION_UNDERLINE_CODE
+        )
+    );
+    assert_eq!(paint(5), format!("\x1b[{}m5\x1b[0m", COLOR_GREEN_CODE));
+}
+
+#[test]
+fn chains_are_independent() {
+    env::set_color_choice(env::ColorChoice::Always);
+    let painted = "X"
+        .if_then(true, |s| s.red())
+        .else_then(|s| s.green())
+        .if_then(false, |s| s.bold())
+        .else_then(|s| s.bg_white());
+    assert_eq!(
+        painted.to_string(),
+        format!("\x1b[{};{}mX\x1b[0m", COLOR_RED_CODE, COLOR_BG_WHITE_CODE)
+    );
+
+    // Without an open chain, else_then does nothing.
+    let painted = "X".blue().else_then(|s| s.green());
+    assert_eq!(
+        painted.to_string(),
+        format!("\x1b[{}mX\x1b[0m", COLOR_BLUE_CODE)
+    );
+}
+
+#[test]
+fn select_arms() {
+    env::set_color_choice(env::ColorChoice::Always);
+    let paint = |load: f64| {
+        format!("{load}")
+            .select(
+                load,
+                &[
+                    (&|v: &f64| *v > 0.9, Style::new().red().bold()),
+                    (&|v: &f64| *v > 0.7, Style::new().yellow()),
+                ],
+            )
+            .to_string()
+    };
+    assert_eq!(paint(0.95), format!("{}0.95\x1b[0m", red_bold()));
+    assert_eq!(paint(0.8), format!("\x1b[{}m0.8\x1b[0m", COLOR_YELLOW_CODE));
+    assert_eq!(paint(0.1), "0.1");
+}
+
+#[test]
+fn groups_follow_comparisons() {
+    env::set_color_choice(env::ColorChoice::Always);
+    let painted = "X"
+        .e_is(false)
+        .if_then(true, |s| s.red().bold())
+        .n_not()
+        .blue();
+    assert_eq!(
+        painted.to_string(),
+        format!("\x1b[{}mX\x1b[0m", COLOR_BLUE_CODE)
+    );
+}
+
+#[test]
+fn style_shortcuts() {
+    assert_eq!(
+        Style::new().bright_cyan(),
+        Style::new().fg(Color::BrightCyan)
+    );
+    assert_eq!(Style::new().bg_magenta(), Style::new().bg(Color::Magenta));
+    assert_eq!(
+        Style::new().italic().invert(),
+        Style::new()
+            .decoration(Decoration::Italic)
+            .decoration(Decoration::Invert)
+    );
+}
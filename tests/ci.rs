@@ -0,0 +1,95 @@
+use rustyhues::env::{self, CiSystem, ColorChoice, ColorLevel};
+use rustyhues::*;
+
+const CI_VARS: [&str; 7] = [
+    "GITHUB_ACTIONS",
+    "GITLAB_CI",
+    "BUILDKITE",
+    "JENKINS_URL",
+    "BUILD_ID",
+    "TF_BUILD",
+    "CI",
+];
+
+fn ci_with(vars: &[(&str, &str)]) -> Option<CiSystem> {
+    for name in CI_VARS {
+        std::env::remove_var(name);
+    }
+    for (name, value) in vars {
+        std::env::set_var(name, value);
+    }
+    env::detect_ci()
+}
+
+// Environment variables and the colour choice are process wide, so everything runs in one test.
+#[test]
+fn ci_detection() {
+    for name in [
+        "NO_COLOR",
+        "CLICOLOR",
+        "CLICOLOR_FORCE",
+        "COLORTERM",
+        "TERM_PROGRAM",
+    ] {
+        std::env::remove_var(name);
+    }
+    std::env::remove_var("WT_SESSION");
+    std::env::set_var("TERM", "xterm");
+    env::set_color_choice(ColorChoice::Auto);
+
+    assert_eq!(ci_with(&[]), None);
+    assert_eq!(ci_with(&[("CI", "true")]), None);
+    assert_eq!(
+        ci_with(&[("CI", "true"), ("GITHUB_ACTIONS", "true")]),
+        Some(CiSystem::GitHubActions)
+    );
+    assert_eq!(ci_with(&[("GITLAB_CI", "true")]), Some(CiSystem::GitLabCi));
+    assert_eq!(ci_with(&[("BUILDKITE", "true")]), Some(CiSystem::Buildkite));
+    assert_eq!(
+        ci_with(&[
+            ("JENKINS_URL", "https://ci.example.com/"),
+            ("BUILD_ID", "42")
+        ]),
+        Some(CiSystem::Jenkins)
+    );
+    assert_eq!(ci_with(&[("JENKINS_URL", "https://ci.example.com/")]), None);
+    assert_eq!(
+        ci_with(&[("TF_BUILD", "True")]),
+        Some(CiSystem::AzurePipelines)
+    );
+    assert_eq!(ci_with(&[("GITHUB_ACTIONS", "false")]), None);
+
+    // CI systems set the colour level, unless the terminal is more specific.
+    ci_with(&[("GITHUB_ACTIONS", "true")]);
+    assert_eq!(env::detect_color_level(), ColorLevel::TrueColor);
+    ci_with(&[("GITLAB_CI", "true")]);
+    assert_eq!(env::detect_color_level(), ColorLevel::Ansi256);
+    ci_with(&[("TF_BUILD", "True")]);
+    assert_eq!(env::detect_color_level(), ColorLevel::Basic16);
+    std::env::set_var("COLORTERM", "truecolor");
+    assert_eq!(env::detect_color_level(), ColorLevel::TrueColor);
+    std::env::remove_var("COLORTERM");
+
+    // stdout and stderr are styled in CI even though they aren't terminals.
+    ci_with(&[("GITHUB_ACTIONS", "true")]);
+    assert!(env::should_colorize(Stream::Stdout));
+    assert!(env::should_colorize(Stream::Stderr));
+    assert_eq!(env::color_level(Stream::Stdout), ColorLevel::TrueColor);
+    assert!(!env::should_colorize(Stream::Custom(false)));
+    assert_eq!(
+        "ok".green().to_string(),
+        format!("\x1b[{COLOR_GREEN_CODE}mok\x1b[0m")
+    );
+
+    // NO_COLOR and ColorChoice::Never still win.
+    std::env::set_var("NO_COLOR", "1");
+    assert!(!env::should_colorize(Stream::Stdout));
+    assert_eq!(env::color_level(Stream::Stdout), ColorLevel::None);
+    std::env::remove_var("NO_COLOR");
+    env::set_color_choice(ColorChoice::Never);
+    assert!(!env::should_colorize(Stream::Stdout));
+    assert_eq!("ok".green().to_string(), "ok");
+    env::set_color_choice(ColorChoice::Auto);
+
+    ci_with(&[]);
+}
@@ -0,0 +1,133 @@
+use rustyhues::env::{self, ColorChoice, ColorLevel};
+use rustyhues::scale::ColorScale;
+use rustyhues::*;
+
+const DETECTION_VARS: [&str; 11] = [
+    "TERM",
+    "COLORTERM",
+    "TERM_PROGRAM",
+    "WT_SESSION",
+    "GITHUB_ACTIONS",
+    "GITLAB_CI",
+    "BUILDKITE",
+    "JENKINS_URL",
+    "TF_BUILD",
+    "CI",
+    "NO_COLOR",
+];
+
+fn detect_with(vars: &[(&str, &str)]) -> ColorLevel {
+    for name in DETECTION_VARS {
+        std::env::remove_var(name);
+    }
+    for (name, value) in vars {
+        std::env::set_var(name, value);
+    }
+    env::detect_color_level()
+}
+
+// Environment variables and the overrides are process wide, so everything runs in one test.
+#[test]
+fn color_levels() {
+    assert_eq!(
+        detect_with(&[("TERM", "xterm-256color"), ("COLORTERM", "truecolor")]),
+        ColorLevel::TrueColor
+    );
+    assert_eq!(
+        detect_with(&[("TERM", "xterm-direct")]),
+        ColorLevel::TrueColor
+    );
+    assert_eq!(
+        detect_with(&[("TERM", "xterm-256color")]),
+        ColorLevel::Ansi256
+    );
+    assert_eq!(
+        detect_with(&[("TERM", "screen-256color")]),
+        ColorLevel::Ansi256
+    );
+    assert_eq!(
+        detect_with(&[("TERM", "xterm-256color"), ("TERM_PROGRAM", "iTerm.app")]),
+        ColorLevel::TrueColor
+    );
+    assert_eq!(
+        detect_with(&[("TERM", "xterm"), ("TERM_PROGRAM", "Apple_Terminal")]),
+        ColorLevel::Ansi256
+    );
+    assert_eq!(
+        detect_with(&[("WT_SESSION", "0f1e")]),
+        ColorLevel::TrueColor
+    );
+    assert_eq!(
+        detect_with(&[("CI", "true"), ("GITHUB_ACTIONS", "true")]),
+        ColorLevel::TrueColor
+    );
+    assert_eq!(detect_with(&[("TERM", "dumb")]), ColorLevel::None);
+    assert_eq!(detect_with(&[("TERM", "xterm")]), ColorLevel::Basic16);
+
+    env::set_color_choice(ColorChoice::Always);
+    assert_eq!(env::color_level(Stream::Stdout), ColorLevel::Basic16);
+    assert!(!env::true_color_allowed());
+    env::set_color_choice(ColorChoice::Never);
+    assert_eq!(env::color_level(Stream::Stdout), ColorLevel::None);
+    env::set_color_choice(ColorChoice::Always);
+
+    // Colours are downgraded to the detected level.
+    detect_with(&[("TERM", "xterm-256color")]);
+    assert_eq!(
+        "x".rgb(255, 0, 0).to_string(),
+        format!("\x1b[{COLOR_FIXED}196mx\x1b[0m")
+    );
+    assert_eq!(
+        "x".fixed(208).to_string(),
+        format!("\x1b[{COLOR_FIXED}208mx\x1b[0m")
+    );
+    assert_eq!(
+        "x".style(ColorScale::Viridis.style(0.0)).to_string(),
+        format!(
+            "\x1b[{COLOR_FIXED}{}mx\x1b[0m",
+            ColorScale::Viridis.sample_fixed(0.0)
+        )
+    );
+    assert_eq!(
+        "x".style(ColorScale::Viridis.style(0.0))
+            .color_level(ColorLevel::TrueColor)
+            .to_string(),
+        "\x1b[38;2;68;1;84mx\x1b[0m"
+    );
+
+    // The global override wins over detection.
+    env::set_color_level_override(Some(ColorLevel::Basic16));
+    assert_eq!(env::color_level_override(), Some(ColorLevel::Basic16));
+    assert_eq!(
+        "x".fixed(196).bg_rgb(0, 0, 238).to_string(),
+        format!("\x1b[{COLOR_RED_CODE};{COLOR_BG_BLUE_CODE}mx\x1b[0m")
+    );
+    assert_eq!(
+        "x".fixed(1).to_string(),
+        format!("\x1b[{COLOR_RED_CODE}mx\x1b[0m")
+    );
+
+    // And a Paint's own level wins over both.
+    assert_eq!(
+        "x".rgb(1, 2, 3)
+            .color_level(ColorLevel::TrueColor)
+            .to_string(),
+        "\x1b[38;2;1;2;3mx\x1b[0m"
+    );
+    assert_eq!("x".red().color_level(ColorLevel::None).to_string(), "x");
+
+    env::set_color_level_override(Some(ColorLevel::TrueColor));
+    assert!(env::true_color_allowed());
+    env::set_true_color_allowed(Some(false));
+    assert!(!env::true_color_allowed());
+    assert_eq!(
+        "x".rgb(255, 255, 255).fixed(208).to_string(),
+        format!("\x1b[{COLOR_BRIGHT_WHITE_CODE};{COLOR_FIXED}208mx\x1b[0m")
+    );
+
+    env::set_true_color_allowed(None);
+    env::set_color_level_override(None);
+    for name in DETECTION_VARS {
+        std::env::remove_var(name);
+    }
+}
@@ -0,0 +1,74 @@
+use rustyhues::*;
+use std::sync::atomic::{AtomicBool, AtomicI64, Ordering};
+use std::sync::Arc;
+
+// predicates_are_skipped_when_disabled switches colours off for a moment, so the checks share
+// one test rather than racing each other.
+#[test]
+fn conditional_styles() {
+    env::set_color_choice(env::ColorChoice::Always);
+    when_is_evaluated_at_display_time();
+    when_value_uses_the_content();
+    negated_and_combined_with_comparisons();
+    predicates_are_skipped_when_disabled();
+}
+
+fn when_is_evaluated_at_display_time() {
+    let highlight = Arc::new(AtomicBool::new(false));
+    let flag = Arc::clone(&highlight);
+    let painted = "X".when(move || flag.load(Ordering::Relaxed)).red();
+
+    assert_eq!(painted.to_string(), "X");
+    highlight.store(true, Ordering::Relaxed);
+    assert_eq!(
+        painted.to_string(),
+        format!("\x1b[{}mX\x1b[0m", COLOR_RED_CODE)
+    );
+}
+
+fn when_value_uses_the_content() {
+    let threshold = Arc::new(AtomicI64::new(10));
+    let limit = Arc::clone(&threshold);
+    let painted = 42
+        .bold()
+        .when_value(move |value| i64::from(*value) > limit.load(Ordering::Relaxed))
+        .red();
+
+    assert_eq!(
+        painted.to_string(),
+        format!("\x1b[{};{}m42\x1b[0m", DECORATION_BOLD_CODE, COLOR_RED_CODE)
+    );
+    threshold.store(100, Ordering::Relaxed);
+    assert_eq!(
+        painted.to_string(),
+        format!("\x1b[{}m42\x1b[0m", DECORATION_BOLD_CODE)
+    );
+}
+
+fn negated_and_combined_with_comparisons() {
+    let painted = (-3).when_value(|value| *value < 0).red().n_not().green();
+    assert_eq!(
+        painted.to_string(),
+        format!("\x1b[{}m-3\x1b[0m", COLOR_RED_CODE)
+    );
+
+    let painted = 3
+        .when_value_not(|value| *value < 0)
+        .green()
+        .when_not(|| true)
+        .red();
+    assert_eq!(
+        painted.to_string(),
+        format!("\x1b[{}m3\x1b[0m", COLOR_GREEN_CODE)
+    );
+}
+
+fn predicates_are_skipped_when_disabled() {
+    env::set_color_choice(env::ColorChoice::Always);
+    let painted = "X".when(|| true).red();
+    env::set_color_choice(env::ColorChoice::Never);
+    let disabled = "X".when(|| panic!("not evaluated")).red();
+    env::set_color_choice(env::ColorChoice::Always);
+    assert_eq!(disabled.to_string(), "X");
+    assert!(painted.to_string().contains(COLOR_RED_CODE));
+}
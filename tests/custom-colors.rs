@@ -90,3 +90,33 @@ fn short_hex_expands_correctly() {
     let painted = format!("{}", "X".hex("F1D"));
     assert!(painted.contains("\x1b[38;2;255;17;221m"));
 }
+
+/* 256 Colour Tests */
+#[test]
+fn foreground_fixed() {
+    env::set_color_choice(env::ColorChoice::Always);
+    env::set_color_level_override(Some(env::ColorLevel::TrueColor));
+    let painted = format!("{}", "X".fixed(208));
+    assert!(painted.contains(&format!("\x1b[{}208m", COLOR_FIXED)))
+}
+
+#[test]
+fn background_fixed() {
+    env::set_color_choice(env::ColorChoice::Always);
+    env::set_color_level_override(Some(env::ColorLevel::TrueColor));
+    let painted = format!("{}", "X".bg_fixed(17));
+    assert!(painted.contains(&format!("\x1b[{}17m", COLOR_BG_FIXED)))
+}
+
+/* Style Tests */
+#[test]
+fn style_applies_all_items() {
+    env::set_color_choice(env::ColorChoice::Always);
+    env::set_color_level_override(Some(env::ColorLevel::TrueColor));
+    let style = Style::new()
+        .fg(Color::Red)
+        .bg_fixed(17)
+        .decoration(Decoration::Bold);
+    let painted = format!("{}", "X".style(style));
+    assert!(painted.starts_with("\x1b[31;48;5;17;1m"))
+}
@@ -0,0 +1,130 @@
+use rustyhues::diff::{diff, Diff};
+use rustyhues::env::PredefinedStyleType;
+use rustyhues::*;
+
+const OLD: &str = "a\nb\nc\nd\ne\nf\ng\nh\ni\nj\n";
+
+#[test]
+fn identical_texts() {
+    assert!(diff(OLD, OLD).is_empty());
+}
+
+#[test]
+fn unified_hunks_with_context() {
+    let new = "a\nb\nC\nd\ne\nf\ng\nh\ni\nj\nk\n";
+    let rendered = Diff::new()
+        .context(1)
+        .labels("old.txt", "new.txt")
+        .diff(OLD, new);
+    assert_eq!(
+        rendered.plain_text(),
+        "--- old.txt\n\
+         +++ new.txt\n\
+         @@ -2,3 +2,3 @@\n \
+         b\n\
+         -c\n\
+         +C\n \
+         d\n\
+         @@ -10,1 +10,2 @@\n \
+         j\n\
+         +k"
+    );
+}
+
+#[test]
+fn nearby_changes_share_a_hunk() {
+    let new = "a\nB\nc\nD\ne\nf\ng\nh\ni\nj\n";
+    let rendered = Diff::new().context(1).word_diff(false).diff(OLD, new);
+    assert_eq!(
+        rendered.plain_text(),
+        "@@ -1,5 +1,5 @@\n a\n-b\n+B\n c\n-d\n+D\n e"
+    );
+}
+
+#[test]
+fn insertions_and_deletions_without_context() {
+    let rendered = Diff::new().context(0).diff("a\nb\n", "a\nx\nb\n");
+    assert_eq!(rendered.plain_text(), "@@ -1,0 +2,1 @@\n+x");
+    let rendered = Diff::new().context(0).diff("a\nb\n", "b\n");
+    assert_eq!(rendered.plain_text(), "@@ -1,1 +0,0 @@\n-a");
+}
+
+#[test]
+fn line_styles() {
+    let success = Style::predefined(PredefinedStyleType::Success);
+    let error = Style::predefined(PredefinedStyleType::Error);
+    let dim = Style::new().decoration(Decoration::Dim);
+    let rendered = Diff::new().diff("keep\nold\n", "keep\n");
+    assert_eq!(
+        rendered.spans(),
+        [
+            Span::new(Style::new().fg(Color::Cyan), "@@ -1,2 +1,1 @@"),
+            Span::plain("\n"),
+            Span::new(dim, " keep"),
+            Span::plain("\n"),
+            Span::new(error, "-old"),
+        ]
+    );
+
+    let insert = Style::new().fg(Color::Blue);
+    let rendered = Diff::new().insert_style(insert).diff("", "new\n");
+    assert_eq!(rendered.spans()[2], Span::new(insert, "+new"));
+    assert_ne!(insert, success);
+}
+
+#[test]
+fn changed_words_are_emphasised() {
+    let insert = Style::new().fg(Color::Green);
+    let delete = Style::new().fg(Color::Red);
+    let rendered = Diff::new()
+        .insert_style(insert)
+        .delete_style(delete)
+        .diff("version = \"1.0\"\n", "version = \"2.0\"\n");
+    let lines = rendered.lines();
+    let emphasis = |color| {
+        Style::new()
+            .fg(Color::Black)
+            .bg(color)
+            .decoration(Decoration::Bold)
+    };
+    assert_eq!(
+        lines[1].spans(),
+        [
+            Span::new(delete, "-version = \""),
+            Span::new(emphasis(Color::Red), "1"),
+            Span::new(delete, ".0\""),
+        ]
+    );
+    assert_eq!(
+        lines[2].spans(),
+        [
+            Span::new(insert, "+version = \""),
+            Span::new(emphasis(Color::Green), "2"),
+            Span::new(insert, ".0\""),
+        ]
+    );
+
+    // Lines with nothing in common aren't emphasised.
+    let rendered = Diff::new()
+        .insert_style(insert)
+        .delete_style(delete)
+        .diff("alpha\n", "beta\n");
+    assert_eq!(rendered.lines()[2].spans(), [Span::new(insert, "+beta")]);
+}
+
+#[test]
+fn large_inputs_use_linear_memory() {
+    // Too big for a single LCS table, so the inputs are split in half first.
+    let old: String = (0..1000)
+        .map(|i| format!("old {i}\nshared {i}\n"))
+        .collect();
+    let new: String = (0..1000)
+        .map(|i| format!("new {i}\nshared {i}\n"))
+        .collect();
+    let rendered = Diff::new().diff(&old, &new).plain_text();
+    let count = |prefix: &str| rendered.lines().filter(|l| l.starts_with(prefix)).count();
+    assert_eq!(count("-old "), 1000);
+    assert_eq!(count("+new "), 1000);
+    assert_eq!(count("-shared "), 0);
+    assert_eq!(count("+shared "), 0);
+}
@@ -306,3 +306,14 @@ fn debug_custom() {
             ))
     )
 }
+
+#[test]
+fn style_patch() {
+    let base = Style::new().red().bg_white().bold();
+    assert_eq!(
+        base.patch(Style::new().blue().underline()),
+        Style::new().blue().bg_white().bold().underline()
+    );
+    assert_eq!(base.patch(Style::new()), base);
+    assert_eq!(Style::new().patch(base), base);
+}
@@ -0,0 +1,192 @@
+use rustyhues::highlight::{Highlighter, Pattern, PatternError};
+use rustyhues::*;
+
+fn red() -> Style {
+    Style::new().fg(Color::Red)
+}
+
+fn blue() -> Style {
+    Style::new().fg(Color::Blue)
+}
+
+fn ranges(pattern: &str, text: &str) -> Vec<&'static str> {
+    let text: &'static str = Box::leak(text.to_string().into_boxed_str());
+    Pattern::new(pattern)
+        .unwrap()
+        .find_all(text)
+        .into_iter()
+        .map(|range| &text[range])
+        .collect()
+}
+
+#[test]
+fn patterns() {
+    assert_eq!(ranges(r"\d+", "a1 b22 c333"), ["1", "22", "333"]);
+    assert_eq!(
+        ranges(r"\b\d+\b", "req-42 took 17ms, 3 retries"),
+        ["42", "3"]
+    );
+    assert_eq!(ranges(r"[a-f0-9]+", "id=deadbeef!"), ["d", "deadbeef"]);
+    assert_eq!(ranges(r"v\d+\.\d+(", "v1.2( v3x4("), ["v1.2("]);
+    assert_eq!(
+        ranges(r"colou?r", "color colour colouur"),
+        ["color", "colour"]
+    );
+    assert_eq!(
+        ranges(r"[^ ]+\.rs", "see main.rs and lib.rs"),
+        ["main.rs", "lib.rs"]
+    );
+    assert_eq!(ranges(r"a.*b", "xa1b2bx"), ["a1b2b"]);
+    assert_eq!(ranges(r"\w+@\w+", "mail bob@example now"), ["bob@example"]);
+    assert_eq!(ranges(r"é+", "caféé"), ["éé"]);
+}
+
+#[test]
+fn nested_repeats_stay_linear() {
+    // A backtracking matcher would try every way of splitting the `a`s between the repeats.
+    let line = "a".repeat(20_000);
+    let pattern = Pattern::new("a*a*a*a*a*a*b").unwrap();
+    let started = std::time::Instant::now();
+    assert!(pattern.find_all(&line).is_empty());
+    assert!(started.elapsed() < std::time::Duration::from_secs(5));
+    assert_eq!(ranges("a*a*a*b", &format!("{line}b")).len(), 1);
+}
+
+#[test]
+fn pattern_errors() {
+    assert_eq!(Pattern::new(""), Err(PatternError::Empty));
+    assert_eq!(
+        Pattern::new("ab[cd"),
+        Err(PatternError::UnclosedClass { position: 2 })
+    );
+    assert_eq!(
+        Pattern::new("ab\\"),
+        Err(PatternError::TrailingEscape { position: 2 })
+    );
+    assert_eq!(
+        Pattern::new("+a"),
+        Err(PatternError::NothingToRepeat { position: 0 })
+    );
+    assert_eq!(
+        Pattern::new("a**"),
+        Err(PatternError::NothingToRepeat { position: 2 })
+    );
+    assert_eq!(
+        PatternError::UnclosedClass { position: 2 }.to_string(),
+        "character class at position 2 is missing a closing `]`"
+    );
+}
+
+#[test]
+fn literals() {
+    let highlighter = Highlighter::new().literal("error", red());
+    assert_eq!(
+        highlighter.highlight("error: another error").spans(),
+        [
+            Span::new(red(), "error"),
+            Span::plain(": another "),
+            Span::new(red(), "error"),
+        ]
+    );
+
+    let highlighter = Highlighter::new().literal_ignore_case("Rust", red());
+    assert_eq!(
+        highlighter.highlight("rust RUST Rust").spans(),
+        [
+            Span::new(red(), "rust"),
+            Span::plain(" "),
+            Span::new(red(), "RUST"),
+            Span::plain(" "),
+            Span::new(red(), "Rust"),
+        ]
+    );
+}
+
+#[test]
+fn overlaps_by_priority() {
+    let text = "req-42 retry 3";
+    let ids = Pattern::new(r"req-\d+").unwrap();
+    let numbers = Pattern::new(r"\d+").unwrap();
+
+    // The first rule wins between rules of the same priority.
+    let highlighter = Highlighter::new()
+        .pattern(numbers.clone(), blue())
+        .pattern(ids.clone(), red());
+    assert_eq!(
+        highlighter.highlight(text).spans(),
+        [
+            Span::plain("req-"),
+            Span::new(blue(), "42"),
+            Span::plain(" retry "),
+            Span::new(blue(), "3"),
+        ]
+    );
+
+    let highlighter = Highlighter::new()
+        .pattern(numbers, blue())
+        .pattern(ids, red())
+        .priority(1);
+    assert_eq!(
+        highlighter.highlight(text).spans(),
+        [
+            Span::new(red(), "req-42"),
+            Span::plain(" retry "),
+            Span::new(blue(), "3"),
+        ]
+    );
+}
+
+#[test]
+fn closures() {
+    let highlighter = Highlighter::new().matcher(
+        |text| {
+            let mut ranges = vec![0..3, 2..2, 100..200];
+            if let Some(start) = text.find('é') {
+                // Not on a character boundary, so ignored.
+                ranges.push(start + 1..start + 2);
+            }
+            ranges
+        },
+        red(),
+    );
+    assert_eq!(
+        highlighter.highlight("abcdé").spans(),
+        [Span::new(red(), "abc"), Span::plain("dé")]
+    );
+}
+
+#[test]
+fn styled_input_keeps_styles() {
+    let mut text = StyledString::styled(Style::new().decoration(Decoration::Bold), "id: ");
+    text.push(Style::new().fg(Color::Green), "abc123");
+    let highlighter = Highlighter::new().pattern(
+        Pattern::new(r"\d+").unwrap(),
+        Style::new().decoration(Decoration::Underline),
+    );
+    assert_eq!(
+        highlighter.highlight_styled(&text).spans(),
+        [
+            Span::new(Style::new().decoration(Decoration::Bold), "id: "),
+            Span::new(Style::new().fg(Color::Green), "abc"),
+            Span::new(
+                Style::new()
+                    .fg(Color::Green)
+                    .decoration(Decoration::Underline),
+                "123"
+            ),
+        ]
+    );
+
+    // Matches can span several input spans.
+    let mut text = StyledString::styled(Style::new().fg(Color::Green), "ab");
+    text.push_str("cd");
+    let highlighter = Highlighter::new().literal("bc", red());
+    assert_eq!(
+        highlighter.highlight_styled(&text).spans(),
+        [
+            Span::new(Style::new().fg(Color::Green), "a"),
+            Span::new(red(), "bc"),
+            Span::plain("d"),
+        ]
+    );
+}
@@ -0,0 +1,98 @@
+use rustyhues::html::{self, Html, HtmlMode};
+use rustyhues::*;
+
+#[test]
+fn plain_text_is_escaped() {
+    assert_eq!(
+        Html::new().pre(false).render("<a href=\"x\">&'</a>"),
+        "&lt;a href=&quot;x&quot;&gt;&amp;&#39;&lt;/a&gt;"
+    );
+}
+
+#[test]
+fn inline_named_colors() {
+    assert_eq!(
+        Html::new().pre(false).render("\x1b[1;31merror\x1b[0m: x"),
+        "<span style=\"color:#cd3131;font-weight:bold\">error</span>: x"
+    );
+}
+
+#[test]
+fn inline_palette_choice() {
+    assert_eq!(
+        Html::new()
+            .pre(false)
+            .palette(Palette::XTERM)
+            .render("\x1b[31mX"),
+        "<span style=\"color:#cd0000\">X</span>"
+    );
+}
+
+#[test]
+fn inline_extended_colors() {
+    assert_eq!(
+        Html::new().pre(false).render("\x1b[38;5;196;48;2;1;2;3mX"),
+        "<span style=\"color:#ff0000;background-color:#010203\">X</span>"
+    );
+}
+
+#[test]
+fn invert_swaps_colors() {
+    assert_eq!(
+        Html::new().pre(false).render("\x1b[7;32mX"),
+        "<span style=\"color:#1e1e1e;background-color:#0dbc79\">X</span>"
+    );
+}
+
+#[test]
+fn classes_mode() {
+    assert_eq!(
+        Html::new()
+            .mode(HtmlMode::Classes)
+            .class_prefix("log")
+            .render("\x1b[4;92;44mX\x1b[0m \x1b[38;2;1;2;3mY"),
+        "<pre class=\"log\"><span class=\"log-fg-bright-green log-bg-blue log-underline\">X</span> \
+         <span style=\"color:#010203\">Y</span></pre>"
+    );
+}
+
+#[test]
+fn class_prefix_is_sanitized() {
+    let html = Html::new()
+        .mode(HtmlMode::Classes)
+        .class_prefix("x\"><script>");
+    assert_eq!(
+        html.render("\x1b[31mX"),
+        "<pre class=\"x---script-\"><span class=\"x---script--fg-red\">X</span></pre>"
+    );
+    assert!(html
+        .stylesheet()
+        .contains(".x---script--fg-red { color: #cd3131; }"));
+}
+
+#[test]
+fn stylesheet_contains_classes() {
+    let css = Html::new().mode(HtmlMode::Classes).stylesheet();
+    assert!(css.contains(".ansi-fg-red { color: #cd3131; }"));
+    assert!(css.contains(".ansi-bg-bright-white { background-color: #ffffff; }"));
+    assert!(css.contains(".ansi-bold { font-weight: bold; }"));
+    assert!(!css.contains("invert"));
+}
+
+#[test]
+fn pre_wrapper_uses_palette_defaults() {
+    assert_eq!(
+        html::to_html("line 1\nline 2"),
+        "<pre style=\"color:#cccccc;background-color:#1e1e1e\">line 1\nline 2</pre>"
+    );
+}
+
+#[test]
+fn renders_styled_strings() {
+    let mut styled = StyledString::new();
+    styled.push(Style::new().decoration(Decoration::Italic), "a < b");
+    assert_eq!(
+        Html::new().pre(false).render_styled(&styled),
+        "<span style=\"font-style:italic\">a &lt; b</span>"
+    );
+}
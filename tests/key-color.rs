@@ -0,0 +1,53 @@
+use rustyhues::env::{self, Background};
+use rustyhues::*;
+
+const KEYS: [&str; 8] = [
+    "web", "db", "worker-1", "worker-2", "cache", "api", "host-a", "host-b",
+];
+
+// The background is global state, so everything depending on it runs in one test.
+#[test]
+fn colors_for_keys() {
+    std::env::set_var("COLORFGBG", "0;15");
+    assert_eq!(env::background(), Background::Light);
+    std::env::set_var("COLORFGBG", "15;default;0");
+    assert_eq!(env::background(), Background::Dark);
+    std::env::set_var("COLORFGBG", "garbage");
+    assert_eq!(env::background(), Background::Dark);
+    std::env::remove_var("COLORFGBG");
+    assert_eq!(env::background(), Background::Dark);
+
+    for key in KEYS {
+        assert_eq!(color_for(&key), color_for(&key.to_string()));
+        assert_eq!(color_for_fixed(&key), color_for(&key).closest_fixed());
+    }
+    assert_eq!(color_for(&("job", 7)), color_for(&("job", 7)));
+
+    let mut colors: Vec<RGB> = KEYS.iter().map(color_for).collect();
+    colors.sort_by_key(|color| color.to_hex());
+    colors.dedup();
+    assert!(colors.len() >= 5);
+
+    let dark: Vec<(RGB, Color)> = KEYS
+        .iter()
+        .map(|key| (color_for(key), color_for_ansi(key)))
+        .collect();
+
+    env::set_background_override(Some(Background::Light));
+    assert_eq!(env::background_override(), Some(Background::Light));
+    for (key, (dark_rgb, dark_ansi)) in KEYS.iter().zip(dark) {
+        assert_ne!(color_for(key), dark_rgb);
+        let light_ansi = color_for_ansi(key);
+        assert_eq!(dark_ansi.index(), light_ansi.index() + 8);
+        assert!(!matches!(light_ansi, Color::Black | Color::White));
+    }
+    env::set_background_override(None);
+}
+
+#[test]
+fn closest_fixed() {
+    assert_eq!(RGB::new(0, 0, 0).closest_fixed(), 16);
+    assert_eq!(RGB::new(255, 255, 255).closest_fixed(), 231);
+    assert_eq!(RGB::new(255, 0, 0).closest_fixed(), 196);
+    assert_eq!(RGB::new(128, 128, 128).closest_fixed(), 244);
+}
@@ -0,0 +1,38 @@
+use rustyhues::env::{self, ColorChoice};
+use rustyhues::*;
+
+// The colour choice is global state, so everything depending on it runs in one test.
+#[test]
+fn lazy_paint_follows_settings_at_display_time() {
+    env::set_color_choice(ColorChoice::Never);
+    let eager = "eager".red();
+    let lazy = "lazy".paint_lazy().red().bold();
+    let lazy_err = "lazy".paint_err_lazy().style(Style::new().green());
+    let rgb = Paint::lazy_stdout("rgb").rgb(255, 0, 0);
+    assert_eq!(lazy.to_string(), "lazy");
+    assert_eq!(lazy_err.to_string(), "lazy");
+
+    env::set_color_choice(ColorChoice::Always);
+    assert_eq!(eager.to_string(), "eager");
+    assert_eq!(
+        lazy.to_string(),
+        format!("\x1b[{COLOR_RED_CODE};{DECORATION_BOLD_CODE}mlazy\x1b[0m")
+    );
+    assert_eq!(
+        lazy_err.to_string(),
+        format!("\x1b[{COLOR_GREEN_CODE}mlazy\x1b[0m")
+    );
+
+    env::set_true_color_allowed(Some(true));
+    assert_eq!(rgb.to_string(), "\x1b[38;2;255;0;0mrgb\x1b[0m");
+    env::set_true_color_allowed(Some(false));
+    assert_eq!(rgb.to_string(), format!("\x1b[{COLOR_RED_CODE}mrgb\x1b[0m"));
+
+    env::set_color_choice(ColorChoice::Never);
+    assert_eq!(lazy.to_string(), "lazy");
+    assert_eq!(
+        StyledString::from(lazy),
+        StyledString::styled(Style::new().red().bold(), "lazy")
+    );
+    env::set_true_color_allowed(None);
+}
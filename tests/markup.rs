@@ -0,0 +1,160 @@
+use rustyhues::*;
+
+fn spans(text: &str) -> Vec<(Style, String)> {
+    markup(text)
+        .unwrap()
+        .into_iter()
+        .map(|span| (span.style, span.text))
+        .collect()
+}
+
+#[test]
+fn open_and_close() {
+    assert_eq!(
+        spans("[bold red]Error:[/] could not open [underline]{}[/]"),
+        vec![
+            (
+                Style::new().fg(Color::Red).decoration(Decoration::Bold),
+                "Error:".to_string()
+            ),
+            (Style::new(), " could not open ".to_string()),
+            (
+                Style::new().decoration(Decoration::Underline),
+                "{}".to_string()
+            ),
+        ]
+    );
+}
+
+#[test]
+fn closing_tags_ignore_case() {
+    assert_eq!(
+        spans("[Red Bold]x[/red BOLD]"),
+        vec![(
+            Style::new().fg(Color::Red).decoration(Decoration::Bold),
+            "x".to_string()
+        )]
+    );
+}
+
+#[test]
+fn nested_tags_add_styles() {
+    assert_eq!(
+        spans("[blue]a[bold]b[/bold]c[/blue]"),
+        vec![
+            (Style::new().fg(Color::Blue), "a".to_string()),
+            (
+                Style::new().fg(Color::Blue).decoration(Decoration::Bold),
+                "b".to_string()
+            ),
+            (Style::new().fg(Color::Blue), "c".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn color_forms() {
+    assert_eq!(spans("[#ff8000]x[/]")[0].0, Style::new().rgb(255, 128, 0));
+    assert_eq!(spans("[rgb(1, 2, 3)]x[/]")[0].0, Style::new().rgb(1, 2, 3));
+    assert_eq!(spans("[color(208)]x[/]")[0].0, Style::new().fixed(208));
+    assert_eq!(
+        spans("[bright_cyan on bright-black]x[/]")[0].0,
+        Style::new().fg(Color::BrightCyan).bg(Color::BrightBlack)
+    );
+    assert_eq!(
+        spans("[on #333]x[/]")[0].0,
+        Style::new().bg_rgb(0x33, 0x33, 0x33)
+    );
+}
+
+#[test]
+fn semantic_tags_use_predefined_styles() {
+    env::reset_predefined_style(env::PredefinedStyleType::Error);
+    assert_eq!(
+        spans("[error]x[/]")[0].0,
+        Style::new().fg(Color::Red).decoration(Decoration::Bold)
+    );
+    assert_eq!(
+        spans("[error underline]x[/]")[0].0,
+        Style::new()
+            .fg(Color::Red)
+            .decoration(Decoration::Bold)
+            .decoration(Decoration::Underline)
+    );
+}
+
+#[test]
+fn escaped_brackets() {
+    assert_eq!(
+        spans("\\[not a tag] \\\\ [bold]x]y[/]"),
+        vec![
+            (Style::new(), "[not a tag] \\ ".to_string()),
+            (Style::new().decoration(Decoration::Bold), "x]y".to_string()),
+        ]
+    );
+    let user_input = "[red]\\";
+    assert_eq!(
+        markup(&format!("[bold]{}[/]", escape_markup(user_input)))
+            .unwrap()
+            .plain_text(),
+        user_input
+    );
+}
+
+#[test]
+fn parse_errors() {
+    assert_eq!(
+        markup("ok [purple]x[/]"),
+        Err(MarkupError::UnknownStyle {
+            position: 3,
+            name: "purple".to_string()
+        })
+    );
+    assert_eq!(
+        markup("ok [bold"),
+        Err(MarkupError::UnterminatedTag { position: 3 })
+    );
+    assert_eq!(markup("[]x"), Err(MarkupError::EmptyTag { position: 0 }));
+    assert_eq!(
+        markup("x[/]"),
+        Err(MarkupError::UnexpectedClose { position: 1 })
+    );
+    assert_eq!(
+        markup("[bold]x[/red]"),
+        Err(MarkupError::MismatchedClose {
+            position: 7,
+            expected: "bold".to_string(),
+            found: "red".to_string()
+        })
+    );
+    assert_eq!(
+        markup("[red) bold]x"),
+        Err(MarkupError::UnknownStyle {
+            position: 0,
+            name: "red)".to_string()
+        })
+    );
+    assert_eq!(
+        markup("[bold]x"),
+        Err(MarkupError::UnclosedTag {
+            position: 0,
+            tag: "bold".to_string()
+        })
+    );
+}
+
+#[test]
+fn error_messages() {
+    let error = markup("[bold]x[/red]").unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "closing tag `[/red]` at position 7 does not match open tag `[bold]`"
+    );
+}
+
+#[test]
+fn displays_like_paint() {
+    env::set_color_choice(env::ColorChoice::Always);
+    let rendered = format!("{}", markup("[blue bold]X[/]").unwrap());
+    assert_eq!(rendered, format!("{}", "X".blue().bold()));
+}
@@ -0,0 +1,103 @@
+use rustyhues::env::{self, PredefinedStyleType};
+use rustyhues::panel::Panel;
+use rustyhues::*;
+
+fn plain(panel: &Panel) -> String {
+    panel.render().plain_text()
+}
+
+#[test]
+fn title_and_footer() {
+    let panel = Panel::new("Hello\nWorld!")
+        .title("Greeting")
+        .footer("v1")
+        .border(BorderStyle::Ascii);
+    assert_eq!(
+        plain(&panel),
+        "+- Greeting -+\n\
+         | Hello      |\n\
+         | World!     |\n\
+         +------- v1 -+"
+    );
+}
+
+#[test]
+fn padding_and_width() {
+    let panel = Panel::new("one two three")
+        .border(BorderStyle::Ascii)
+        .padding(2)
+        .vertical_padding(1)
+        .width(13);
+    assert_eq!(
+        plain(&panel),
+        "+-----------+\n\
+         |           |\n\
+         |  one two  |\n\
+         |  three    |\n\
+         |           |\n\
+         +-----------+"
+    );
+}
+
+#[test]
+fn long_title_is_truncated() {
+    let panel = Panel::new("x")
+        .title("A very long title")
+        .border(BorderStyle::Ascii)
+        .width(12);
+    assert_eq!(plain(&panel), "+- A ver… -+\n| x        |\n+----------+");
+}
+
+#[test]
+fn narrow_panel_drops_the_title() {
+    let panel = Panel::new("x")
+        .title("Title")
+        .footer("v1")
+        .border(BorderStyle::Ascii)
+        .width(5);
+    assert_eq!(plain(&panel), "+---+\n| x |\n+---+");
+}
+
+#[test]
+fn without_border() {
+    let panel = Panel::new("body").title("Title").border(BorderStyle::None);
+    assert_eq!(plain(&panel), "  Title  \n body    ");
+}
+
+#[test]
+fn border_and_title_styles() {
+    let panel = Panel::new("oops")
+        .title("Error")
+        .semantic(PredefinedStyleType::Error)
+        .border(BorderStyle::Ascii);
+    let error = Style::predefined(PredefinedStyleType::Error);
+    let rendered = panel.render();
+    let spans = rendered.spans();
+    assert_eq!(spans[0], Span::new(error, "+-"));
+    assert_eq!(spans[2], Span::new(error, "Error"));
+
+    // A title with its own colour keeps it.
+    let panel = Panel::new("oops")
+        .title(StyledString::from("Error".blue()))
+        .border_style(Style::new().fg(Color::Red))
+        .border(BorderStyle::Ascii);
+    let rendered = panel.render();
+    assert_eq!(rendered.spans()[2].style, Style::new().fg(Color::Blue));
+}
+
+#[test]
+fn unicode_fallback() {
+    let panel = Panel::new("x");
+
+    env::set_unicode_override(Some(true));
+    assert!(env::unicode_supported());
+    assert_eq!(plain(&panel), "╭───╮\n│ x │\n╰───╯");
+
+    env::set_unicode_override(Some(false));
+    assert!(!env::unicode_supported());
+    assert_eq!(plain(&panel), "+---+\n| x |\n+---+");
+    assert_eq!(BorderStyle::Heavy.fallback(), BorderStyle::Ascii);
+    assert_eq!(BorderStyle::None.fallback(), BorderStyle::None);
+
+    env::set_unicode_override(None);
+}
@@ -0,0 +1,134 @@
+use rustyhues::*;
+
+fn spans(text: &str) -> Vec<(Style, String)> {
+    parse_ansi(text)
+        .into_iter()
+        .map(|span| (span.style, span.text))
+        .collect()
+}
+
+#[test]
+fn plain_text_is_one_span() {
+    assert_eq!(spans("hello"), vec![(Style::new(), "hello".to_string())]);
+    assert!(parse_ansi("").is_empty());
+}
+
+#[test]
+fn basic_colors_and_reset() {
+    assert_eq!(
+        spans("\x1b[31mred\x1b[0m plain \x1b[104mbg\x1b[m"),
+        vec![
+            (Style::new().fg(Color::Red), "red".to_string()),
+            (Style::new(), " plain ".to_string()),
+            (Style::new().bg(Color::BrightBlue), "bg".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn inverse_of_paint() {
+    env::set_color_choice(env::ColorChoice::Always);
+    env::set_true_color_allowed(Some(true));
+    let painted = format!("{}", "X".blue().bg_white().bold().rgb(1, 2, 3));
+    let parsed = parse_ansi(&painted);
+    assert_eq!(
+        parsed.spans(),
+        &[Span::new(
+            Style::new()
+                .set_rgb(RGB::new(1, 2, 3))
+                .bg(Color::White)
+                .decoration(Decoration::Bold),
+            "X"
+        )]
+    );
+}
+
+#[test]
+fn extended_colors_with_semicolons() {
+    assert_eq!(
+        spans("\x1b[38;5;196;48;2;10;20;30mX"),
+        vec![(Style::new().fixed(196).bg_rgb(10, 20, 30), "X".to_string())]
+    );
+}
+
+#[test]
+fn extended_colors_with_colons() {
+    let expected = Style::new().rgb(1, 2, 3).bg_fixed(42);
+    assert_eq!(spans("\x1b[38:2::1:2:3;48:5:42mX")[0].0, expected);
+    assert_eq!(spans("\x1b[38:2:1:2:3;48:5:42mX")[0].0, expected);
+}
+
+#[test]
+fn decorations_on_and_off() {
+    assert_eq!(
+        spans("\x1b[1;2;3;4;7mA\x1b[22;23mB\x1b[4:0;27mC"),
+        vec![
+            (
+                Style::new()
+                    .decoration(Decoration::Bold)
+                    .decoration(Decoration::Dim)
+                    .decoration(Decoration::Italic)
+                    .decoration(Decoration::Underline)
+                    .decoration(Decoration::Invert),
+                "A".to_string()
+            ),
+            (
+                Style::new()
+                    .decoration(Decoration::Underline)
+                    .decoration(Decoration::Invert),
+                "B".to_string()
+            ),
+            (Style::new(), "C".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn default_colors_reset_one_side() {
+    assert_eq!(
+        spans("\x1b[31;42mA\x1b[39mB\x1b[49mC"),
+        vec![
+            (
+                Style::new().fg(Color::Red).bg(Color::Green),
+                "A".to_string()
+            ),
+            (Style::new().bg(Color::Green), "B".to_string()),
+            (Style::new(), "C".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn non_sgr_sequences_are_dropped() {
+    assert_eq!(
+        spans("\x1b[2K\x1b[31mA\x1b]8;;https://example.com\x1b\\B\x1b[?25l"),
+        vec![(Style::new().fg(Color::Red), "AB".to_string())]
+    );
+}
+
+#[test]
+fn invalid_extended_colors_are_ignored() {
+    assert_eq!(spans("\x1b[38;5;300mX")[0].0, Style::new());
+    assert_eq!(spans("\x1b[38;2;1mX")[0].0, Style::new());
+}
+
+#[test]
+fn to_ansi_round_trips() {
+    let text = "\x1b[1;31merror\x1b[0m: \x1b[38;5;12mfile.rs\x1b[0m";
+    let parsed = parse_ansi(text);
+    assert_eq!(parse_ansi(&parsed.to_ansi()), parsed);
+    assert_eq!(parsed.plain_text(), "error: file.rs");
+    assert_eq!(parsed.width(), 14);
+}
+
+#[test]
+fn styled_string_lines_keep_styles() {
+    let parsed = parse_ansi("\x1b[32mone\ntwo\x1b[0m\nthree");
+    let lines = parsed.lines();
+    assert_eq!(lines.len(), 3);
+    assert_eq!(
+        lines[1].spans(),
+        &[Span::new(Style::new().fg(Color::Green), "two")]
+    );
+    assert_eq!(lines[2].plain_text(), "three");
+}
@@ -0,0 +1,194 @@
+use rustyhues::pretty::{pretty_json, Json, JsonError, KeyValue, MAX_DEPTH};
+use rustyhues::table::Align;
+use rustyhues::*;
+
+const TEXT: &str = r#" {"name": "rustyhues", "version": 1.5e3, "tags": ["color", "cli"],
+    "stable": true, "parent": null, "empty": {}, "none": [] } "#;
+
+#[test]
+fn pretty_print() {
+    let json = pretty_json(TEXT).unwrap();
+    assert_eq!(
+        json.plain_text(),
+        r#"{
+  "name": "rustyhues",
+  "version": 1.5e3,
+  "tags": [
+    "color",
+    "cli"
+  ],
+  "stable": true,
+  "parent": null,
+  "empty": {},
+  "none": []
+}"#
+    );
+}
+
+#[test]
+fn indent_and_compact() {
+    let json = Json::new().indent(4).render(r#"{"a":[1,-2.5]}"#).unwrap();
+    assert_eq!(
+        json.plain_text(),
+        "{\n    \"a\": [\n        1,\n        -2.5\n    ]\n}"
+    );
+
+    let json = Json::new().compact(true).render(TEXT).unwrap();
+    assert_eq!(
+        json.plain_text(),
+        r#"{"name":"rustyhues","version":1.5e3,"tags":["color","cli"],"stable":true,"parent":null,"empty":{},"none":[]}"#
+    );
+}
+
+#[test]
+fn token_styles() {
+    let key = Style::new().fg(Color::Magenta);
+    let punctuation = Style::new().decoration(Decoration::Dim);
+    let json = Json::new()
+        .key_style(key)
+        .punctuation_style(punctuation)
+        .compact(true)
+        .render(r#"{"a":"b","c":[1,false,null]}"#)
+        .unwrap();
+    assert_eq!(
+        json.spans(),
+        [
+            Span::new(punctuation, "{"),
+            Span::new(key, "\"a\""),
+            Span::new(punctuation, ":"),
+            Span::new(Style::new().fg(Color::Green), "\"b\""),
+            Span::new(punctuation, ","),
+            Span::new(key, "\"c\""),
+            Span::new(punctuation, ":["),
+            Span::new(Style::new().fg(Color::Cyan), "1"),
+            Span::new(punctuation, ","),
+            Span::new(Style::new().fg(Color::Yellow), "false"),
+            Span::new(punctuation, ","),
+            Span::new(Style::new().fg(Color::BrightBlack), "null"),
+            Span::new(punctuation, "]}"),
+        ]
+    );
+}
+
+#[test]
+fn strings_are_kept_as_written() {
+    let json = pretty_json(r#"["tab\there \u00e9 \"quoted\" 日本"]"#).unwrap();
+    assert_eq!(
+        json.plain_text(),
+        "[\n  \"tab\\there \\u00e9 \\\"quoted\\\" 日本\"\n]"
+    );
+}
+
+#[test]
+fn errors() {
+    assert_eq!(pretty_json(""), Err(JsonError::UnexpectedEnd));
+    assert_eq!(pretty_json(r#"{"a": 1"#), Err(JsonError::UnexpectedEnd));
+    assert_eq!(
+        pretty_json(r#"{"a" 1}"#),
+        Err(JsonError::UnexpectedCharacter {
+            position: 5,
+            found: '1'
+        })
+    );
+    assert_eq!(
+        pretty_json("[1 2]"),
+        Err(JsonError::UnexpectedCharacter {
+            position: 3,
+            found: '2'
+        })
+    );
+    assert_eq!(
+        pretty_json("{1: 2}"),
+        Err(JsonError::UnexpectedCharacter {
+            position: 1,
+            found: '1'
+        })
+    );
+    assert_eq!(
+        pretty_json("[01]"),
+        Err(JsonError::InvalidNumber { position: 1 })
+    );
+    assert_eq!(
+        pretty_json("1."),
+        Err(JsonError::InvalidNumber { position: 0 })
+    );
+    assert_eq!(
+        pretty_json(r#""a\qb""#),
+        Err(JsonError::InvalidEscape { position: 2 })
+    );
+    assert_eq!(
+        pretty_json("\"a\nb\""),
+        Err(JsonError::ControlCharacter { position: 2 })
+    );
+    assert_eq!(
+        pretty_json("true false"),
+        Err(JsonError::TrailingCharacters { position: 5 })
+    );
+    assert_eq!(
+        pretty_json("nul"),
+        Err(JsonError::UnexpectedCharacter {
+            position: 0,
+            found: 'n'
+        })
+    );
+    let nested = format!("{}{}", "[".repeat(MAX_DEPTH), "]".repeat(MAX_DEPTH));
+    assert!(pretty_json(&nested).is_ok());
+    assert_eq!(
+        pretty_json(&format!("[{nested}]")),
+        Err(JsonError::TooDeep {
+            position: MAX_DEPTH
+        })
+    );
+    assert_eq!(
+        pretty_json(&"[".repeat(200_000)),
+        Err(JsonError::TooDeep {
+            position: MAX_DEPTH
+        })
+    );
+    assert_eq!(
+        JsonError::InvalidNumber { position: 1 }.to_string(),
+        "invalid number at position 1"
+    );
+}
+
+#[test]
+fn key_value_listing() {
+    let listing = KeyValue::new()
+        .entry("Name", "rustyhues")
+        .entry("License", "MIT")
+        .entry("Authors", "Ada\nGrace");
+    assert_eq!(
+        listing.render().plain_text(),
+        "Name:    rustyhues\n\
+         License: MIT\n\
+         Authors: Ada\n         \
+         Grace"
+    );
+
+    let listing = KeyValue::new()
+        .entry("a", "1")
+        .entry("bcd", "2")
+        .separator(" =")
+        .align_keys(Align::Right);
+    assert_eq!(listing.render().plain_text(), "  a = 1\nbcd = 2");
+
+    let listing = KeyValue::new()
+        .entry("a", "1")
+        .entry("bcd", "2")
+        .separator("：");
+    assert_eq!(listing.render().plain_text(), "a：   1\nbcd： 2");
+}
+
+#[test]
+fn key_value_styles() {
+    let bold = Style::new().decoration(Decoration::Bold);
+    let listing = KeyValue::new().entry("Status", StyledString::from("ok".green()));
+    assert_eq!(
+        listing.render().spans(),
+        [
+            Span::new(bold, "Status"),
+            Span::plain(": "),
+            Span::new(Style::new().fg(Color::Green), "ok"),
+        ]
+    );
+}
@@ -0,0 +1,173 @@
+use rustyhues::env::{self, PredefinedStyleType};
+use rustyhues::progress::{Clock, ProgressBar, Spinner};
+use rustyhues::*;
+use std::io::{self, Write};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+#[derive(Clone, Default)]
+struct FakeClock(Arc<Mutex<Duration>>);
+
+impl FakeClock {
+    fn advance(&self, duration: Duration) {
+        *self.0.lock().unwrap() += duration;
+    }
+}
+
+impl Clock for FakeClock {
+    fn now(&self) -> Duration {
+        *self.0.lock().unwrap()
+    }
+}
+
+#[derive(Clone, Default)]
+struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+impl Buffer {
+    fn contents(&self) -> String {
+        String::from_utf8(self.0.lock().unwrap().clone()).unwrap()
+    }
+}
+
+impl Write for Buffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+fn bar(clock: &FakeClock, buffer: &Buffer) -> ProgressBar {
+    ProgressBar::new(10)
+        .clock(clock.clone())
+        .writer(buffer.clone())
+        .bar_width(10)
+}
+
+#[test]
+fn template_fields() {
+    env::set_unicode_override(Some(false));
+    let clock = FakeClock::default();
+    let buffer = Buffer::default();
+    let mut progress = bar(&clock, &buffer)
+        .template("[{bar}] {pos}/{len} {percent} {elapsed} ETA {eta} {per_sec} {unknown} {msg}")
+        .interactive(false);
+    assert_eq!(
+        progress.render().plain_text(),
+        "[----------] 0/10 0% 0s ETA - - {unknown}"
+    );
+
+    clock.advance(Duration::from_secs(8));
+    progress.set_position(4);
+    progress.set_message("copying");
+    assert_eq!(
+        progress.render().plain_text(),
+        "[####------] 4/10 40% 8s ETA 12s 0.5/s {unknown} copying"
+    );
+
+    clock.advance(Duration::from_secs(112));
+    assert_eq!(
+        progress.render().plain_text(),
+        "[####------] 4/10 40% 2m00s ETA 3m00s 0.0/s {unknown} copying"
+    );
+}
+
+#[test]
+fn eta_too_far_away() {
+    let clock = FakeClock::default();
+    let mut progress = ProgressBar::new(u64::MAX)
+        .clock(clock.clone())
+        .writer(Buffer::default())
+        .template("ETA {eta}");
+    progress.inc(1);
+    clock.advance(Duration::from_secs(2));
+    assert_eq!(progress.render().plain_text(), "ETA -");
+}
+
+#[test]
+fn plain_lines_are_throttled() {
+    let clock = FakeClock::default();
+    let buffer = Buffer::default();
+    let mut progress = bar(&clock, &buffer)
+        .template("{pos}/{len} {msg}")
+        .interactive(false)
+        .plain_interval(Duration::from_secs(5));
+
+    progress.inc(1);
+    clock.advance(Duration::from_secs(1));
+    progress.inc(1);
+    clock.advance(Duration::from_secs(4));
+    progress.inc(1);
+    progress.inc(1);
+    progress.finish_with_message("done");
+
+    assert_eq!(buffer.contents(), "1/10\n3/10\n10/10 done\n");
+}
+
+#[test]
+fn interactive_redraws_with_styles() {
+    env::set_unicode_override(Some(false));
+    let clock = FakeClock::default();
+    let buffer = Buffer::default();
+    let mut progress = bar(&clock, &buffer)
+        .template("{spinner} {pos}/{len}")
+        .interactive(true);
+
+    progress.inc(5);
+    clock.advance(Duration::from_millis(10));
+    progress.inc(1);
+    progress.fail("broken");
+
+    let info = Style::predefined(PredefinedStyleType::Info);
+    let error = Style::predefined(PredefinedStyleType::Error);
+    let mut first = StyledString::styled(info, "|");
+    first.push_str(" 5/10");
+    let mut last = StyledString::styled(error, "x");
+    last.push_str(" 6/10");
+    assert_eq!(
+        buffer.contents(),
+        format!("\r\x1b[2K{}\r\x1b[2K{}\n", first.to_ansi(), last.to_ansi())
+    );
+}
+
+#[test]
+fn bar_spinner_animates() {
+    env::set_unicode_override(Some(false));
+    let clock = FakeClock::default();
+    let buffer = Buffer::default();
+    let mut progress = bar(&clock, &buffer)
+        .template("{spinner} {pos}")
+        .interactive(false)
+        .plain_interval(Duration::ZERO);
+
+    progress.inc(1);
+    progress.tick();
+    progress.inc(1);
+    progress.finish();
+
+    assert_eq!(buffer.contents(), "| 1\n/ 1\n- 2\n+ 10\n");
+}
+
+#[test]
+fn spinner() {
+    let clock = FakeClock::default();
+    let buffer = Buffer::default();
+    let mut spinner = Spinner::new()
+        .template("{spinner} {msg} ({elapsed})")
+        .clock(clock.clone())
+        .writer(buffer.clone())
+        .interactive(false)
+        .plain_interval(Duration::ZERO);
+
+    spinner.set_message("resolving");
+    clock.advance(Duration::from_secs(65));
+    spinner.finish_with_message("resolved");
+
+    let lines: Vec<String> = buffer.contents().lines().map(String::from).collect();
+    assert_eq!(lines.len(), 2);
+    assert!(lines[0].ends_with(" resolving (0s)"));
+    assert!(lines[1].ends_with(" resolved (1m05s)"));
+}
@@ -0,0 +1,183 @@
+use rustyhues::env::{ColorLevel, PredefinedStyleType};
+use rustyhues::scale::{ColorScale, Gradient, Scale, StylizeNumber, Thresholds};
+use rustyhues::*;
+
+fn latency() -> Thresholds {
+    Thresholds::new()
+        .below(100.0, Style::new().green())
+        .below(500.0, Style::new().yellow())
+        .otherwise(Style::new().red())
+}
+
+#[test]
+fn thresholds() {
+    let scale = latency();
+    assert_eq!(scale.style_for(12.0), Style::new().green());
+    assert_eq!(scale.style_for(100.0), Style::new().yellow());
+    assert_eq!(scale.style_for(499.9), Style::new().yellow());
+    assert_eq!(scale.style_for(500.0), Style::new().red());
+    assert_eq!(scale.style_for(f64::NAN), Style::new().red());
+    assert_eq!(Thresholds::new().style_for(1.0), Style::new());
+}
+
+#[test]
+fn signed_deltas_with_semantic_styles() {
+    let scale = Thresholds::new()
+        .below(0.0, PredefinedStyleType::Error)
+        .at_most(0.0, Style::new().dim())
+        .otherwise(PredefinedStyleType::Success);
+    assert_eq!(
+        scale.style_for(-2.0),
+        Style::predefined(PredefinedStyleType::Error)
+    );
+    assert_eq!(scale.style_for(0.0), Style::new().dim());
+    assert_eq!(
+        scale.style_for(0.5),
+        Style::predefined(PredefinedStyleType::Success)
+    );
+}
+
+#[test]
+fn gradient_interpolation() {
+    let gradient = Gradient::new((0.0, RGB::new(255, 0, 0)), (100.0, RGB::new(0, 255, 0)))
+        .stop(50.0, RGB::new(255, 255, 0));
+    assert_eq!(gradient.color_at(-10.0), RGB::new(255, 0, 0));
+    assert_eq!(gradient.color_at(0.0), RGB::new(255, 0, 0));
+    assert_eq!(gradient.color_at(25.0), RGB::new(255, 128, 0));
+    assert_eq!(gradient.color_at(50.0), RGB::new(255, 255, 0));
+    assert_eq!(gradient.color_at(75.0), RGB::new(128, 255, 0));
+    assert_eq!(gradient.color_at(150.0), RGB::new(0, 255, 0));
+    assert_eq!(gradient.color_at(f64::NAN), RGB::new(255, 0, 0));
+
+    assert_eq!(
+        gradient.style_for(100.0),
+        Style::new().set_rgb(RGB::new(0, 255, 0))
+    );
+    assert_eq!(
+        gradient.background(true).style_for(100.0),
+        Style::new().set_bg_rgb(RGB::new(0, 255, 0))
+    );
+}
+
+#[test]
+fn stylize_numbers() {
+    env::set_color_choice(env::ColorChoice::Always);
+    assert_eq!(
+        42.scaled(&latency()).to_string(),
+        format!("\x1b[{}m42\x1b[0m", COLOR_GREEN_CODE)
+    );
+    assert_eq!(
+        750u64.scaled(&latency()).to_string(),
+        format!("\x1b[{}m750\x1b[0m", COLOR_RED_CODE)
+    );
+    assert_eq!(
+        250.5f32.scaled_err(&latency()).to_string(),
+        format!("\x1b[{}m250.5\x1b[0m", COLOR_YELLOW_CODE)
+    );
+
+    let scales: [&dyn Scale; 2] = [
+        &latency(),
+        &Gradient::new((0.0, RGB::new(0, 0, 0)), (1.0, RGB::new(255, 255, 255))),
+    ];
+    assert_eq!(scales[0].style_for(1.0), Style::new().green());
+}
+
+const COLOR_SCALES: [ColorScale; 5] = [
+    ColorScale::Viridis,
+    ColorScale::Magma,
+    ColorScale::Inferno,
+    ColorScale::Cividis,
+    ColorScale::RedBlue,
+];
+
+#[test]
+fn color_scale_samples() {
+    assert_eq!(ColorScale::Viridis.sample(0.0), RGB::new(0x44, 0x01, 0x54));
+    assert_eq!(ColorScale::Viridis.sample(1.0), RGB::new(0xfd, 0xe7, 0x25));
+    assert_eq!(ColorScale::Viridis.sample(0.5), RGB::new(0x21, 0x91, 0x8c));
+    assert_eq!(ColorScale::Magma.sample(0.0), RGB::new(0x00, 0x00, 0x04));
+    assert_eq!(ColorScale::RedBlue.sample(0.5), RGB::new(0xf7, 0xf7, 0xf7));
+
+    // Halfway between the first two stops.
+    assert_eq!(
+        ColorScale::Viridis.sample(0.0625),
+        RGB::new(0x46, 0x17, 0x68)
+    );
+
+    for scale in COLOR_SCALES {
+        assert_eq!(scale.sample(-1.0), scale.sample(0.0));
+        assert_eq!(scale.sample(2.0), scale.sample(1.0));
+        assert_eq!(scale.sample(f64::NAN), scale.sample(0.0));
+    }
+}
+
+#[test]
+fn color_scale_bands_stay_distinguishable() {
+    for scale in COLOR_SCALES {
+        let steps: Vec<f64> = (0..=100).map(|i| i as f64 / 100.0).collect();
+
+        let mut fixed: Vec<u8> = steps.iter().map(|t| scale.sample_fixed(*t)).collect();
+        fixed.dedup();
+        let mut unique = fixed.clone();
+        unique.sort_unstable();
+        unique.dedup();
+        assert!(fixed.len() >= 10, "{scale:?}");
+        assert_eq!(unique.len(), fixed.len(), "{scale:?}");
+
+        let mut ansi: Vec<Color> = steps.iter().map(|t| scale.sample_ansi(*t)).collect();
+        ansi.dedup();
+        assert!(ansi.len() >= 5, "{scale:?}");
+        for (i, color) in ansi.iter().enumerate() {
+            assert!(!ansi[i + 1..].contains(color), "{scale:?}");
+        }
+    }
+    assert_eq!(ColorScale::Viridis.sample_fixed(0.0), 53);
+    assert_eq!(ColorScale::Viridis.sample_fixed(1.0), 184);
+    assert_eq!(ColorScale::RedBlue.sample_ansi(0.0), Color::Red);
+    assert_eq!(ColorScale::RedBlue.sample_ansi(0.5), Color::White);
+    assert_eq!(ColorScale::RedBlue.sample_ansi(1.0), Color::Blue);
+}
+
+#[test]
+fn color_scale_styles() {
+    let top = AdaptiveColor {
+        rgb: RGB::new(0xfc, 0xfd, 0xbf),
+        fixed: ColorScale::Magma.sample_fixed(1.0),
+        named: Color::BrightWhite,
+    };
+    assert_eq!(
+        ColorScale::Magma.style_for(1.0).fg,
+        Some(StyleColor::Adaptive(top))
+    );
+    assert_eq!(
+        ColorScale::Magma.bg_style(1.0).bg,
+        Some(StyleColor::Adaptive(top))
+    );
+
+    // The colour is picked from the level the Paint is displayed at.
+    let paint = |level| {
+        Paint::new_for_stream("x", Stream::Custom(true))
+            .style(ColorScale::Magma.style(1.0))
+            .color_level(level)
+            .to_string()
+    };
+    assert_eq!(
+        paint(ColorLevel::TrueColor),
+        "\x1b[38;2;252;253;191mx\x1b[0m"
+    );
+    assert_eq!(
+        paint(ColorLevel::Ansi256),
+        format!("\x1b[{COLOR_FIXED}{}mx\x1b[0m", top.fixed)
+    );
+    assert_eq!(
+        paint(ColorLevel::Basic16),
+        format!("\x1b[{COLOR_BRIGHT_WHITE_CODE}mx\x1b[0m")
+    );
+    assert_eq!(
+        Paint::new_for_stream("x", Stream::Custom(true))
+            .style(ColorScale::Viridis.bg_style(0.5))
+            .color_level(ColorLevel::Basic16)
+            .to_string(),
+        format!("\x1b[{COLOR_BG_GREEN_CODE}mx\x1b[0m")
+    );
+}
@@ -0,0 +1,77 @@
+use rustyhues::env::{self, ColorChoice, ColorLevel};
+use rustyhues::*;
+
+// Environment variables and the colour choice are process wide, so everything runs in one test.
+#[test]
+fn detection_for_other_destinations() {
+    std::env::remove_var("CLICOLOR_FORCE");
+    std::env::remove_var("NO_COLOR");
+    std::env::remove_var("CLICOLOR");
+    std::env::remove_var("COLUMNS");
+    env::set_color_choice(ColorChoice::Auto);
+
+    assert!(env::should_colorize(Stream::Custom(true)));
+    assert!(!env::should_colorize(Stream::Custom(false)));
+    assert_eq!(
+        "ok".paint_for(Stream::Custom(true)).green().to_string(),
+        format!("\x1b[{COLOR_GREEN_CODE}mok\x1b[0m")
+    );
+    assert_eq!(
+        Paint::new_for_stream("ok", Stream::Custom(false))
+            .green()
+            .to_string(),
+        "ok"
+    );
+    assert_eq!(env::terminal_size(Stream::Custom(true)), None);
+
+    env::set_color_level_override(Some(ColorLevel::Ansi256));
+    assert_eq!(env::color_level(Stream::Custom(true)), ColorLevel::Ansi256);
+    assert_eq!(env::color_level(Stream::Custom(false)), ColorLevel::None);
+    assert_eq!(
+        "ok".paint_for(Stream::Custom(true))
+            .rgb(255, 0, 0)
+            .to_string(),
+        "\x1b[38;5;196mok\x1b[0m"
+    );
+    assert_eq!(
+        Paint::lazy_for_stream("ok", Stream::Custom(true))
+            .color_level(ColorLevel::Basic16)
+            .rgb(255, 0, 0)
+            .to_string(),
+        format!("\x1b[{COLOR_RED_CODE}mok\x1b[0m")
+    );
+    assert_eq!(
+        Paint::lazy_for_stream("ok", Stream::Custom(false))
+            .color_level(ColorLevel::TrueColor)
+            .rgb(255, 0, 0)
+            .to_string(),
+        "ok"
+    );
+    env::set_color_level_override(None);
+
+    std::env::set_var("NO_COLOR", "1");
+    assert!(!env::should_colorize(Stream::Custom(true)));
+    std::env::remove_var("NO_COLOR");
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::io::AsRawFd;
+
+        let path = std::env::temp_dir().join(format!("rustyhues-{}.log", std::process::id()));
+        let file = std::fs::File::create(&path).unwrap();
+        assert!(!env::should_colorize(Stream::Fd(file.as_raw_fd())));
+        assert_eq!(Paint::for_writer("log", &file).red().to_string(), "log");
+        assert_eq!(env::terminal_size(Stream::Fd(file.as_raw_fd())), None);
+
+        let lazy = Paint::lazy_for_stream("log", Stream::Fd(file.as_raw_fd())).red();
+        env::set_color_choice(ColorChoice::Always);
+        assert_eq!(
+            lazy.to_string(),
+            format!("\x1b[{COLOR_RED_CODE}mlog\x1b[0m")
+        );
+        env::set_color_choice(ColorChoice::Auto);
+
+        drop(file);
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,93 @@
+use rustyhues::svg::{self, Chrome, Svg};
+use rustyhues::*;
+
+#[test]
+fn output_is_deterministic() {
+    let text = "\x1b[1;32mCompiling\x1b[0m rustyhues\n\x1b[31merror\x1b[0m: <oops>\n";
+    assert_eq!(svg::to_svg(text), svg::to_svg(text));
+}
+
+#[test]
+fn size_follows_content() {
+    let image = Svg::new()
+        .chrome(Chrome::None)
+        .font_size(10.0)
+        .line_height(2.0)
+        .padding(0.0)
+        .render("abcd\nab\n");
+    assert!(image.starts_with(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"24\" height=\"40\" viewBox=\"0 0 24 40\">"
+    ));
+}
+
+#[test]
+fn fixed_columns() {
+    let image = Svg::new()
+        .chrome(Chrome::None)
+        .font_size(10.0)
+        .padding(5.0)
+        .columns(80)
+        .render("x");
+    assert!(image.contains("width=\"490\""));
+}
+
+#[test]
+fn spans_become_tspans() {
+    let image = Svg::new()
+        .chrome(Chrome::None)
+        .padding(0.0)
+        .font_size(10.0)
+        .render("\x1b[1;31mab\x1b[0m cd");
+    assert!(image.contains(
+        "<tspan x=\"0\" fill=\"#cd3131\" font-weight=\"bold\">ab</tspan><tspan x=\"12\"> cd</tspan>"
+    ));
+}
+
+#[test]
+fn control_characters_are_dropped() {
+    let image = Svg::new()
+        .chrome(Chrome::None)
+        .padding(0.0)
+        .font_size(10.0)
+        .render("a\x07b\x08c\td");
+    assert!(image.contains("<tspan x=\"0\">abc\td</tspan>"));
+}
+
+#[test]
+fn background_colors_become_rects() {
+    let image = Svg::new()
+        .chrome(Chrome::None)
+        .padding(0.0)
+        .font_size(10.0)
+        .line_height(2.0)
+        .render("\x1b[44mab\x1b[0m");
+    assert!(image.contains("<rect x=\"0\" y=\"0\" width=\"12\" height=\"20\" fill=\"#2472c8\"/>"));
+}
+
+#[test]
+fn text_is_escaped() {
+    let image = Svg::new().title("a & b").render("<tag>");
+    assert!(image.contains("&lt;tag&gt;"));
+    assert!(image.contains(">a &amp; b</text>"));
+}
+
+#[test]
+fn window_chrome_and_palette() {
+    let image = Svg::new()
+        .palette(Palette::XTERM)
+        .font_family("Fira Code")
+        .render("x");
+    assert!(image.contains("fill=\"#ff5f56\""));
+    assert!(image.contains("fill=\"#000000\""));
+    assert!(image.contains("font-family=\"Fira Code\""));
+
+    let plain = Svg::new().chrome(Chrome::None).render("x");
+    assert!(!plain.contains("<circle"));
+}
+
+#[test]
+fn renders_styled_strings() {
+    let styled = StyledString::styled(Style::new().decoration(Decoration::Underline), "u");
+    let image = Svg::new().render_styled(&styled);
+    assert!(image.contains("text-decoration=\"underline\">u</tspan>"));
+}
@@ -0,0 +1,168 @@
+use rustyhues::table::{Align, Overflow, Table};
+use rustyhues::*;
+
+fn plain(table: &Table) -> String {
+    table.render().plain_text()
+}
+
+#[test]
+fn light_borders_with_header() {
+    let table = Table::new()
+        .header(["Name", "Size"])
+        .row(["a.txt", "12"])
+        .row(["b.txt", "3456"]);
+    assert_eq!(
+        plain(&table),
+        "┌───────┬──────┐\n\
+         │ Name  │ Size │\n\
+         ├───────┼──────┤\n\
+         │ a.txt │ 12   │\n\
+         │ b.txt │ 3456 │\n\
+         └───────┴──────┘"
+    );
+}
+
+#[test]
+fn border_styles() {
+    let table = Table::new().row(["x"]);
+    assert_eq!(
+        plain(&table.clone().border(BorderStyle::Ascii)),
+        "+---+\n| x |\n+---+"
+    );
+    assert_eq!(
+        plain(&table.clone().border(BorderStyle::Heavy)),
+        "┏━━━┓\n┃ x ┃\n┗━━━┛"
+    );
+    assert_eq!(
+        plain(&table.border(BorderStyle::Rounded)),
+        "╭───╮\n│ x │\n╰───╯"
+    );
+}
+
+#[test]
+fn no_borders() {
+    let table = Table::new()
+        .border(BorderStyle::None)
+        .header(["key", "value"])
+        .row(["a", "1"]);
+    assert_eq!(plain(&table), "key  value\na    1    ");
+}
+
+#[test]
+fn alignment() {
+    let table = Table::new()
+        .border(BorderStyle::Ascii)
+        .row(["left", "right", "center"])
+        .row(["a", "b", "c"])
+        .align(1, Align::Right)
+        .align(2, Align::Center);
+    assert_eq!(
+        plain(&table),
+        "+------+-------+--------+\n\
+         | left | right | center |\n\
+         | a    |     b |   c    |\n\
+         +------+-------+--------+"
+    );
+}
+
+#[test]
+fn measures_visible_width() {
+    env::set_color_choice(env::ColorChoice::Always);
+    let table = Table::new()
+        .border(BorderStyle::Ascii)
+        .row([StyledString::from("red".red()), "日本".into()]);
+    assert_eq!(
+        plain(&table),
+        "+-----+------+\n| red | 日本 |\n+-----+------+"
+    );
+    assert!(table.render().to_ansi().contains("\x1b[31mred\x1b[0m"));
+}
+
+#[test]
+fn max_width_wraps_or_truncates() {
+    let wrapped = Table::new()
+        .border(BorderStyle::Ascii)
+        .row(["one two three", "x"])
+        .max_width(0, 7, Overflow::Wrap);
+    assert_eq!(
+        plain(&wrapped),
+        "+---------+---+\n\
+         | one two | x |\n\
+         | three   |   |\n\
+         +---------+---+"
+    );
+
+    let truncated = Table::new()
+        .border(BorderStyle::Ascii)
+        .row(["one two three"])
+        .max_width(0, 7, Overflow::Truncate);
+    assert_eq!(plain(&truncated), "+---------+\n| one tw… |\n+---------+");
+}
+
+#[test]
+fn header_and_zebra_styles() {
+    let header = Style::new().decoration(Decoration::Bold);
+    let zebra = Style::new().bg(Color::BrightBlack);
+    let table = Table::new()
+        .border(BorderStyle::None)
+        .header(["h"])
+        .row(["a"])
+        .row([StyledString::styled(Style::new().bg(Color::Red), "b")])
+        .header_style(header)
+        .zebra(zebra);
+    let lines = table.render().lines();
+    assert_eq!(lines[0].spans(), &[Span::new(header, "h")]);
+    assert_eq!(lines[1].spans(), &[Span::plain("a")]);
+    assert_eq!(
+        lines[2].spans(),
+        &[Span::new(Style::new().bg(Color::Red), "b")]
+    );
+}
+
+#[test]
+fn styled_borders() {
+    let dim = Style::new().decoration(Decoration::Dim);
+    let table = Table::new()
+        .border(BorderStyle::Ascii)
+        .border_style(dim)
+        .row(["x"]);
+    let lines = table.render().lines();
+    assert_eq!(lines[0].spans(), &[Span::new(dim, "+---+")]);
+    assert_eq!(
+        lines[1].spans(),
+        &[Span::new(dim, "|"), Span::plain(" x "), Span::new(dim, "|")]
+    );
+}
+
+#[test]
+fn short_rows_are_padded() {
+    let table = Table::new()
+        .border(BorderStyle::Ascii)
+        .row(["a", "b"])
+        .row(["c"]);
+    assert_eq!(plain(&table), "+---+---+\n| a | b |\n| c |   |\n+---+---+");
+}
+
+#[test]
+fn display_is_plain_when_color_disabled() {
+    env::set_color_choice(env::ColorChoice::Never);
+    let table = Table::new()
+        .border(BorderStyle::None)
+        .row([StyledString::styled(Style::new().fg(Color::Green), "ok")]);
+    assert_eq!(table.to_string(), "ok");
+    env::set_color_choice(env::ColorChoice::Always);
+}
+
+#[test]
+fn styled_string_from_paint_ignores_terminal_detection() {
+    let paint = Paint::new_for_stream("x", Stream::Custom(false))
+        .red()
+        .is(Some(false))
+        .underline()
+        .not(Some(false))
+        .bold();
+    assert_eq!(
+        StyledString::from(paint),
+        StyledString::styled(Style::new().red().bold(), "x")
+    );
+}
@@ -0,0 +1,45 @@
+use rustyhues::env::{self, TerminalSize};
+use rustyhues::Stream;
+use std::io::IsTerminal;
+
+#[test]
+fn override_and_env_fallback() {
+    let size = TerminalSize {
+        columns: 132,
+        rows: 43,
+    };
+    env::set_terminal_size_override(Some(size));
+    assert_eq!(env::terminal_size_override(), Some(size));
+    assert_eq!(env::terminal_size(Stream::Stdout), Some(size));
+    assert_eq!(env::terminal_size(Stream::Stderr), Some(size));
+
+    env::set_terminal_size_override(None);
+    assert_eq!(env::terminal_size_override(), None);
+
+    // The environment is only used when stdout isn't connected to a terminal.
+    if !std::io::stdout().is_terminal() {
+        std::env::set_var("COLUMNS", "100");
+        std::env::set_var("LINES", "30");
+        assert_eq!(
+            env::terminal_size(Stream::Stdout),
+            Some(TerminalSize {
+                columns: 100,
+                rows: 30
+            })
+        );
+
+        std::env::remove_var("LINES");
+        assert_eq!(
+            env::terminal_size(Stream::Stdout),
+            Some(TerminalSize {
+                columns: 100,
+                rows: 24
+            })
+        );
+
+        std::env::set_var("COLUMNS", "wide");
+        assert_eq!(env::terminal_size(Stream::Stdout), None);
+        std::env::remove_var("COLUMNS");
+        assert_eq!(env::terminal_size(Stream::Stdout), None);
+    }
+}
@@ -0,0 +1,119 @@
+#![cfg(feature = "terminfo")]
+
+use rustyhues::env::{self, ColorLevel};
+use rustyhues::terminfo::{search_dirs, TermInfo, TermInfoError};
+use std::path::{Path, PathBuf};
+
+fn fixtures() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/terminfo")
+}
+
+fn fixture(name: &str) -> TermInfo {
+    TermInfo::load_path(fixtures().join("r").join(name)).unwrap()
+}
+
+#[test]
+fn legacy_format() {
+    let info = fixture("rh-16");
+    assert_eq!(
+        info.names(),
+        ["rh-16", "rustyhues test terminal with 8 colours"]
+    );
+    assert!(info.flag("am"));
+    assert!(!info.flag("bce"));
+    assert_eq!(info.number("cols"), Some(80));
+    assert_eq!(info.number("lines"), Some(24));
+    assert_eq!(info.colors(), Some(8));
+    assert_eq!(info.number("pairs"), Some(64));
+    assert_eq!(info.string("setaf"), Some(&b"\x1b[3%p1%dm"[..]));
+    assert_eq!(info.string("op"), Some(&b"\x1b[39;49m"[..]));
+    assert_eq!(info.string("setf"), None);
+    assert!(!info.true_color());
+    assert_eq!(info.color_level(), ColorLevel::Basic16);
+
+    assert_eq!(fixture("rh-256").color_level(), ColorLevel::Ansi256);
+    assert_eq!(fixture("rh-256").number("pairs"), Some(32767));
+
+    let mono = fixture("rh-mono");
+    assert_eq!(mono.colors(), None);
+    assert_eq!(mono.string("sgr0"), Some(&b"\x1b[0m"[..]));
+    assert_eq!(mono.color_level(), ColorLevel::None);
+}
+
+#[test]
+fn extended_capabilities() {
+    let info = fixture("rh-tc");
+    assert!(info.flag("Tc"));
+    assert_eq!(info.colors(), Some(256));
+    assert!(info.true_color());
+    assert_eq!(info.color_level(), ColorLevel::TrueColor);
+}
+
+#[test]
+fn number32_format() {
+    let info = fixture("rh-direct");
+    assert_eq!(info.colors(), Some(0x1000000));
+    assert_eq!(info.number("pairs"), Some(0x10000));
+    assert!(info.flag("RGB"));
+    assert!(info
+        .string("setaf")
+        .is_some_and(|setaf| setaf.starts_with(b"\x1b[%?%p1%{8}%<")));
+    assert_eq!(info.color_level(), ColorLevel::TrueColor);
+}
+
+#[test]
+fn invalid_entries() {
+    assert_eq!(
+        TermInfo::parse(&[0x1a, 0x02, 0, 0]),
+        Err(TermInfoError::InvalidMagic(0o1032))
+    );
+    assert_eq!(
+        TermInfo::parse(&[0x1a, 0x01, 10, 0]),
+        Err(TermInfoError::Truncated)
+    );
+
+    let bytes = std::fs::read(fixtures().join("r/rh-16")).unwrap();
+    assert_eq!(
+        TermInfo::parse(&bytes[..bytes.len() - 4]),
+        Err(TermInfoError::Truncated)
+    );
+}
+
+// `TERMINFO` is process wide, so lookups through the search path run in one test.
+#[test]
+fn search_path_and_detection() {
+    std::env::set_var("TERMINFO", fixtures());
+    std::env::remove_var("TERMINFO_DIRS");
+    assert_eq!(search_dirs()[0], fixtures());
+    assert!(search_dirs().contains(&PathBuf::from("/usr/share/terminfo")));
+
+    assert_eq!(TermInfo::load("rh-256").unwrap().colors(), Some(256));
+    assert_eq!(
+        TermInfo::load("rh-missing"),
+        Err(TermInfoError::NotFound {
+            name: "rh-missing".to_string()
+        })
+    );
+    assert!(TermInfo::load("../r/rh-256").is_err());
+
+    for name in [
+        "COLORTERM",
+        "TERM_PROGRAM",
+        "WT_SESSION",
+        "GITHUB_ACTIONS",
+        "GITLAB_CI",
+        "BUILDKITE",
+        "JENKINS_URL",
+        "TF_BUILD",
+        "CI",
+    ] {
+        std::env::remove_var(name);
+    }
+    std::env::set_var("TERM", "rh-tc");
+    assert_eq!(TermInfo::from_env().unwrap().names()[0], "rh-tc");
+    assert_eq!(env::detect_color_level(), ColorLevel::TrueColor);
+    std::env::set_var("TERM", "rh-16");
+    assert_eq!(env::detect_color_level(), ColorLevel::Basic16);
+    std::env::set_var("TERM", "rh-mono");
+    assert_eq!(env::detect_color_level(), ColorLevel::None);
+}
@@ -0,0 +1,103 @@
+use rustyhues::tree::{Node, Tree};
+use rustyhues::*;
+
+fn sample() -> Node {
+    Node::new("my-app")
+        .child(
+            Node::new("serde")
+                .child(Node::new("serde_derive").child(Node::new("syn")))
+                .child(Node::new("itoa")),
+        )
+        .child(Node::new("rustyhues").child(Node::new("libc")))
+}
+
+#[test]
+fn unicode_guides() {
+    let tree = Tree::new(sample()).ascii(false);
+    assert_eq!(
+        tree.render().plain_text(),
+        "my-app\n\
+         ├── serde\n\
+         │   ├── serde_derive\n\
+         │   │   └── syn\n\
+         │   └── itoa\n\
+         └── rustyhues\n    \
+             └── libc"
+    );
+}
+
+#[test]
+fn ascii_guides() {
+    let tree = Tree::new(sample()).ascii(true);
+    assert_eq!(
+        tree.render().plain_text(),
+        "my-app\n\
+         |-- serde\n\
+         |   |-- serde_derive\n\
+         |   |   `-- syn\n\
+         |   `-- itoa\n\
+         `-- rustyhues\n    \
+             `-- libc"
+    );
+}
+
+#[test]
+fn collapse_by_depth() {
+    let tree = Tree::new(sample()).ascii(true).max_depth(1);
+    assert_eq!(
+        tree.render().plain_text(),
+        "my-app\n|-- serde (+2)\n`-- rustyhues (+1)"
+    );
+
+    let root = Node::new("root").child(Node::new("a").child(Node::new("b")).collapsed(true));
+    assert_eq!(
+        Tree::new(root).ascii(true).render().plain_text(),
+        "root\n`-- a (+1)"
+    );
+}
+
+#[test]
+fn multiline_labels() {
+    let root = Node::new("root")
+        .child(Node::new("first\nline two"))
+        .child(Node::new("last\nline two"));
+    assert_eq!(
+        Tree::new(root).ascii(true).render().plain_text(),
+        "root\n|-- first\n|   line two\n`-- last\n    line two"
+    );
+
+    let root = Node::new("root\nline two").child(
+        Node::new("parent\nline two")
+            .child(Node::new("child"))
+            .child(Node::new("last")),
+    );
+    assert_eq!(
+        Tree::new(root).ascii(true).render().plain_text(),
+        "root\n\
+         |   line two\n\
+         `-- parent\n    \
+             |   line two\n    \
+             |-- child\n    \
+             `-- last"
+    );
+}
+
+#[test]
+fn guides_are_dimmed_and_labels_keep_styles() {
+    let root = Node::new("root").child(Node::new(StyledString::from("child".green())));
+    let rendered = Tree::new(root).ascii(true).render();
+    let dim = Style::new().decoration(Decoration::Dim);
+    assert_eq!(
+        rendered.spans(),
+        [
+            Span::plain("root\n"),
+            Span::new(dim, "`-- "),
+            Span::new(Style::new().fg(Color::Green), "child"),
+        ]
+    );
+
+    let root = Node::new("root").child(Node::new("child"));
+    let guide = Style::new().fg(Color::BrightBlack);
+    let rendered = Tree::new(root).ascii(true).guide_style(guide).render();
+    assert_eq!(rendered.spans()[1], Span::new(guide, "`-- "));
+}
@@ -0,0 +1,75 @@
+use rustyhues::*;
+
+fn cut(text: &str, options: TruncateOptions) -> String {
+    truncate(&StyledString::from(text), &options).plain_text()
+}
+
+#[test]
+fn short_text_is_unchanged() {
+    assert_eq!(cut("short", TruncateOptions::new(5)), "short");
+}
+
+#[test]
+fn truncate_end() {
+    assert_eq!(
+        cut("/home/user/src/main.rs", TruncateOptions::new(8)),
+        "/home/u…"
+    );
+}
+
+#[test]
+fn truncate_start() {
+    let options = TruncateOptions::new(8).position(TruncatePosition::Start);
+    assert_eq!(cut("/home/user/src/main.rs", options), "…main.rs");
+}
+
+#[test]
+fn truncate_middle() {
+    let options = TruncateOptions::new(9).position(TruncatePosition::Middle);
+    assert_eq!(cut("/home/user/src/main.rs", options), "/hom…n.rs");
+}
+
+#[test]
+fn custom_ellipsis() {
+    assert_eq!(
+        cut("abcdefghij", TruncateOptions::new(6).ellipsis("...")),
+        "abc..."
+    );
+    assert_eq!(
+        cut("abcdefghij", TruncateOptions::new(4).ellipsis("")),
+        "abcd"
+    );
+    assert_eq!(
+        cut("abcdefghij", TruncateOptions::new(2).ellipsis("...")),
+        ".."
+    );
+}
+
+#[test]
+fn wide_characters_are_not_split() {
+    assert_eq!(cut("日本語テキスト", TruncateOptions::new(6)), "日本…");
+    let options = TruncateOptions::new(6).position(TruncatePosition::Start);
+    assert_eq!(cut("日本語テキスト", options), "…スト");
+}
+
+#[test]
+fn styled_ellipsis() {
+    let dim = Style::new().decoration(Decoration::Dim);
+    let options = TruncateOptions::new(4).ellipsis(StyledString::styled(dim, "~"));
+    let text = StyledString::styled(Style::new().fg(Color::Blue), "abcdef");
+    assert_eq!(
+        truncate(&text, &options).spans(),
+        &[
+            Span::new(Style::new().fg(Color::Blue), "abc"),
+            Span::new(dim, "~")
+        ]
+    );
+}
+
+#[test]
+fn ansi_escapes_stay_balanced() {
+    let text = "\x1b[31mred \x1b[1mbold red\x1b[0m";
+    let truncated = truncate_ansi(text, &TruncateOptions::new(6));
+    assert_eq!(truncated, "\x1b[31mred \x1b[0m\x1b[31;1mb\x1b[0m…");
+    assert_eq!(visible_width(&truncated), 6);
+}
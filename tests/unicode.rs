@@ -0,0 +1,31 @@
+use rustyhues::env;
+
+// Detection reads process wide environment variables, so it is checked in a single test and
+// the variables are put back afterwards.
+#[test]
+fn unicode_detection() {
+    let saved: Vec<_> = ["TERM", "LANG", "LC_ALL", "LC_CTYPE"]
+        .into_iter()
+        .map(|name| (name, std::env::var_os(name)))
+        .collect();
+    env::set_unicode_override(None);
+
+    std::env::set_var("TERM", "xterm-256color");
+    std::env::remove_var("LC_ALL");
+    std::env::remove_var("LC_CTYPE");
+    std::env::set_var("LANG", "en_US.UTF-8");
+    assert!(env::unicode_supported());
+    std::env::set_var("LC_ALL", "C");
+    assert!(!env::unicode_supported());
+    std::env::set_var("LC_ALL", "de_DE.utf8");
+    assert!(env::unicode_supported());
+    std::env::set_var("TERM", "dumb");
+    assert!(!env::unicode_supported());
+
+    for (name, value) in saved {
+        match value {
+            Some(value) => std::env::set_var(name, value),
+            None => std::env::remove_var(name),
+        }
+    }
+}
@@ -0,0 +1,88 @@
+use rustyhues::*;
+
+fn plain(lines: &[StyledString]) -> Vec<String> {
+    lines.iter().map(StyledString::plain_text).collect()
+}
+
+#[test]
+fn wraps_on_word_boundaries() {
+    let text = StyledString::from("the quick brown fox jumps over the lazy dog");
+    assert_eq!(
+        plain(&wrap(&text, &WrapOptions::new(10))),
+        vec!["the quick", "brown fox", "jumps over", "the lazy", "dog"]
+    );
+}
+
+#[test]
+fn keeps_existing_newlines() {
+    let text = StyledString::from("one two\n\nthree");
+    assert_eq!(
+        plain(&wrap(&text, &WrapOptions::new(20))),
+        vec!["one two", "", "three"]
+    );
+}
+
+#[test]
+fn splits_long_words() {
+    let text = StyledString::from("a abcdefghij b");
+    assert_eq!(
+        plain(&wrap(&text, &WrapOptions::new(4))),
+        vec!["a", "abcd", "efgh", "ij b"]
+    );
+}
+
+#[test]
+fn counts_wide_characters() {
+    let text = StyledString::from("日本語 テキスト");
+    assert_eq!(
+        plain(&wrap(&text, &WrapOptions::new(8))),
+        vec!["日本語", "テキスト"]
+    );
+}
+
+#[test]
+fn hanging_indent_and_prefix() {
+    let text = StyledString::from("first second third fourth");
+    let options = WrapOptions::new(14)
+        .initial_indent("note: ")
+        .subsequent_indent("      ");
+    assert_eq!(
+        plain(&wrap(&text, &options)),
+        vec!["note: first", "      second", "      third", "      fourth"]
+    );
+}
+
+#[test]
+fn styles_carry_across_lines() {
+    let red = Style::new().fg(Color::Red);
+    let text = StyledString::styled(red, "aaa bbb");
+    let lines = wrap(&text, &WrapOptions::new(3));
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[0].spans(), &[Span::new(red, "aaa")]);
+    assert_eq!(lines[1].spans(), &[Span::new(red, "bbb")]);
+}
+
+#[test]
+fn styled_indent() {
+    let bullet = StyledString::styled(Style::new().fg(Color::Cyan), "* ");
+    let lines = wrap(&"item".into(), &WrapOptions::new(10).initial_indent(bullet));
+    assert_eq!(
+        lines[0].spans(),
+        &[
+            Span::new(Style::new().fg(Color::Cyan), "* "),
+            Span::plain("item")
+        ]
+    );
+}
+
+#[test]
+fn ansi_lines_reopen_styles() {
+    let wrapped = wrap_ansi("\x1b[1;32mgreen words here\x1b[0m", &WrapOptions::new(11));
+    assert_eq!(
+        wrapped,
+        "\x1b[32;1mgreen words\x1b[0m\n\x1b[32;1mhere\x1b[0m"
+    );
+    for line in wrapped.lines() {
+        assert!(visible_width(line) <= 11);
+    }
+}